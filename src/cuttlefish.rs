@@ -3,16 +3,39 @@ use iced::{Result, Application, Settings, Alignment, Length, executor};
 use iced::widget::{Button, Row, Column, Container, Text, Scrollable, Rule};
 use iced::Color;
 use iced_style::theme;
-use lib_cfg::{get_cfg_data, BindKey, ShortcutKey, BarWidget, WindowAnimation, WorkAnimation, Border, decode_header, decode_pri, decode_theme, decode_win_anim, decode_work_anim, decode_blur, decode_widget};
+use lib_cfg::{get_cfg_data, BindKey, ShortcutKey, BarWidget, WindowAnimation, WorkAnimation, Border, AutostartEntry, Keybind, Dispatcher, CfgWarning, CfgError, FileData, Workspaces, WorkspaceKeys, Directions, DirectionKeys, MonitorCfg, Transform, Input, FollowMouse, Layout, LayoutEngine, MasterOrientation, WindowRule, RuleAction, RuleState, Animations, AnimationName, decode_theme, list_profiles};
 mod lib_cfg;
+mod lib_render;
+mod lib_backup;
+mod lib_diff;
+mod lib_import;
+mod lib_monitors;
+mod lib_xkb;
+mod lib_clients;
 use gettextrs::*;
 use gettextrs::gettext as tr;
-use oceania_style::{ButtonStyle, ListStyle, make_custom_theme, MenuStyle, SelectedTheme, ThemeCustom, ThemeSet};
+use oceania_style::{ButtonStyle, ListStyle, make_custom_theme, MenuStyle, SelectedTheme, TextStyle, ThemeCustom, ThemeSet};
 use rfd::FileDialog;
+use lib_backup::Backup;
+use lib_diff::DiffLine;
+use lib_monitors::Output;
+use lib_xkb::{XkbRules, read_xkb_rules};
+use lib_clients::Client;
+use cuttlefish_preview::FilePreview;
+use cuttlefish_animations::bezier_text;
+use cuttlefish_history::TextField;
 
 mod cuttlefish_pages;
 mod kb_parser;
 mod cuttlefish_save_helper;
+mod cuttlefish_cli;
+mod cuttlefish_history;
+mod cuttlefish_restore;
+mod cuttlefish_preview;
+mod cuttlefish_monitors;
+mod cuttlefish_input;
+mod cuttlefish_rules;
+mod cuttlefish_animations;
 
 
 //This is Cuttlefish, Our Configuration Tool
@@ -21,33 +44,32 @@ const SIDEBAR_WIDTH: u16 = 175;
 fn main() -> Result {
     let _ = textdomain("CuttlefishCfg");
     let _ = bind_textdomain_codeset("CuttlefishCfg", "UTF-8");
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.len() > 0 { //any arguments mean headless mode, the gui takes none
+        std::process::exit(cuttlefish_cli::run_cli(args));
+    }
     Configurator::run(Settings::default())
 }
 
 
 struct Configurator { //The basic configurator struct, contains most program state
     theme: SelectedTheme,
+    cfg_version: u32, //the layout cfg.toml was read in, one newer than CFG_VERSION is never saved over
     current_page: Page,
     wallpaper: String,
     primary_key: Option<ShortcutKey>,
     secondary_key: Option<ShortcutKey>,
-    exit_header: Option<BindKey>,
-    exit_key: String,
-    launch_header: Option<BindKey>,
-    launch_key: String,
-    kill_header: Option<BindKey>,
-    kill_key: String,
-    minimize_header: Option<BindKey>,
-    minimize_key: String,
-    scratch_header: Option<BindKey>,
-    scratch_key: String,
+    binds: Vec<Keybind>,
     unsaved: bool,
     capture_next: Option<CaptureInput>,
-    index: u8,
-    index_max: u8,
+    index: usize,
+    index_max: usize,
     border: Border,
     window_anim: Option<WindowAnimation>,
     work_anim: Option<WorkAnimation>,
+    animations: Animations,
+    bezier_inputs: Vec<String>, //the points of each curve as typed, they only reach animations once they parse
+    bezier_names: Vec<String>, //the names as typed, one already taken is kept out of animations
     blur: bool,
     theme_set: ThemeSet,
     width: ShrinkValue,
@@ -55,15 +77,38 @@ struct Configurator { //The basic configurator struct, contains most program sta
     bar_center: Vec<BarWidget>,
     bar_right: Vec<BarWidget>,
     next_widget: Option<BarWidget>,
+    workspaces: Workspaces,
+    directions: Directions,
+    layout: Layout,
+    window_rules: Vec<WindowRule>,
+    clients: Vec<Client>, //what hyprctl reported when last asked, not saved
+    monitors: Vec<MonitorCfg>,
+    outputs: Vec<Output>, //what hyprctl reported when last asked, not saved
+    input: Input,
+    xkb: XkbRules, //empty without xkb data, the input page then takes typed codes
+    autostart: Vec<AutostartEntry>,
+    autostart_input: String,
+    profile: String, //empty when no profile is active
+    profiles: Vec<String>,
+    profile_input: String,
+    cfg_warnings: Vec<CfgWarning>, //problems found while reading cfg.toml, shown on the main page
+    errors: Vec<CfgError>, //shown above every page until dismissed
+    snapshot: FileData, //the settings as of the last change, compared against after every update
+    history: Vec<FileData>,
+    future: Vec<FileData>, //undone snapshots, cleared by any new change
+    history_field: Option<TextField>, //the text box the last undo step was typed into
+    pending_leftover: String, //what an import couldn't take over, added to usercfg.conf by the next save
+    backups: Vec<Backup>, //newest first
+    backup_selected: Option<usize>,
+    backup_diff: Vec<(String, Vec<DiffLine>)>, //path and the changes restoring the selected backup would make
+    preview: Option<Vec<FilePreview>>, //open while a save waits to be confirmed
+    preview_full: Option<usize>, //the file shown in full rather than as a diff
 }
 #[derive(PartialEq, Debug, Clone)]
 enum CaptureInput { //enum used to store what binding should be captured into
     NoKey,//TODO: REMOVE THIS IT'S STUPID
-    ExitKey,
-    LaunchKey,
-    KillKey,
-    MiniKey,
-    ScratchKey
+    Bind(usize),
+    SubmapKey
 }
 #[derive(PartialEq, Debug, Clone)]
 enum WidgetBank {
@@ -78,138 +123,174 @@ enum ShrinkValue {
     Tiny
 }
 
+fn theme_set() -> ThemeSet { //the built-in light and dark themes and the user's custom one
+    ThemeSet {
+        light: ThemeCustom {
+            application: theme::Palette {
+                background: Color::from_rgb8(0xE0, 0xF5, 0xFF),
+                text: Color::from_rgb8(0x00, 0x19, 0x36),
+                primary: Color::from_rgb8(0x00, 0x77, 0xFF),
+                success: Color::from_rgb8(0x00, 0xCB, 0x40),
+                danger: Color::from_rgb8(0xFF, 0x4C, 0x00),
+            },
+            sidebar: ButtonStyle { 
+                border_radius: 2.0,
+                txt_color: Color::from_rgb8( 0x00, 0x19, 0x36),
+                bg_color: Color::from_rgb8(0xD2, 0xF0, 0xFF),
+                border_color: Color::from_rgb8(0, 0, 0),
+                border_width: 0.0,
+                shadow_offset: iced::Vector {x: 0.0, y: 0.0}
+            },
+            secondary: ButtonStyle {
+                border_radius: 2.0,
+                txt_color: Color::from_rgb8(0x00, 0x20, 0x46),
+                bg_color: Color::from_rgb8(0xC6, 0xEC, 0xFF),
+                border_color: Color::from_rgb8(0, 0, 0),
+                border_width: 0.0,
+                shadow_offset: iced::Vector {x: 0.0, y: 0.0}
+            },
+            list: ListStyle {
+                txt_color: Color::from_rgb8( 0x00, 0x19, 0x36),
+                bg_color: Color::from_rgb8(0xE0, 0xF5, 0xFF),
+                handle_color: Color::from_rgb8( 0x00, 0x19, 0x36),
+                border_radius: 5.0,
+                border_width: 2.0,
+                border_color: Color::from_rgb8( 0x00, 0x19, 0x36),
+                menu: MenuStyle {
+                    txt_color: Color::from_rgb8( 0x00, 0x19, 0x36),
+                    bg_color: Color::from_rgb8(0xE0, 0xF5, 0xFF),
+                    border_radius: 5.0,
+                    border_width: 2.0,
+                    border_color: Color::from_rgb8( 0x00, 0x19, 0x36),
+                    sel_txt_color: Color::from_rgb8( 0x00, 0x19, 0x36),
+                    sel_bg_color: Color::from_rgb8(0x00, 0xF1, 0xD6),
+                }
+            }
+        },
+        dark: ThemeCustom { // TODO: set dark theme properly
+            application: theme::Palette {
+                background: Color::from_rgb8(0x00, 0x19, 0x36),
+                text: Color::from_rgb8(0xE0, 0xF5, 0xFF),
+                primary: Color::from_rgb8(0x00, 0xAB, 0xE1),
+                success: Color::from_rgb8(0x00, 0xA9, 0x35),
+                danger: Color::from_rgb8(0xC5, 0x3A, 0x00),
+            },
+            sidebar: ButtonStyle { 
+                border_radius: 2.0,
+                txt_color: Color::from_rgb8( 0xE0, 0xF5, 0xFF),
+                bg_color: Color::from_rgb8(0x00, 0x20, 0x46),
+                border_color: Color::from_rgb8(0, 0, 0),
+                border_width: 0.0,
+                shadow_offset: iced::Vector {x: 0.0, y: 0.0}
+            },
+            secondary: ButtonStyle {
+                border_radius: 2.0,
+                txt_color: Color::from_rgb8(0xE0, 0xF5, 0xFF),
+                bg_color: Color::from_rgb8(0x00, 0x29, 0x58),
+                border_color: Color::from_rgb8(0, 0, 0),
+                border_width: 0.0,
+                shadow_offset: iced::Vector {x: 0.0, y: 0.0}
+            },
+            list: ListStyle {
+                txt_color: Color::from_rgb8(0xE0, 0xF5, 0xFF),
+                bg_color: Color::from_rgb8(0x00, 0x29, 0x58),
+                handle_color: Color::from_rgb8(0xE0, 0xF5, 0xFF),
+                border_radius: 5.0,
+                border_width: 2.0,
+                border_color: Color::from_rgb8(0xE0, 0xF5, 0xFF),
+                menu: MenuStyle {
+                    txt_color: Color::from_rgb8(0xE0, 0xF5, 0xFF),
+                    bg_color: Color::from_rgb8(0xE0, 0xF5, 0xFF),
+                    border_radius: 5.0,
+                    border_width: 2.0,
+                    border_color: Color::from_rgb8(0xE0, 0xF5, 0xFF),
+                    sel_txt_color: Color::from_rgb8(0xE0, 0xF5, 0xFF),
+                    sel_bg_color: Color::from_rgb8(0x00, 0xCD, 0xB6),
+                }
+            }
+        },
+        custom: make_custom_theme()
+    }
+}
 impl Default for Configurator {
     fn default() -> Self {
-        let data = get_cfg_data();
-        let mut left_widgets = vec![];
-        let mut center_widgets = vec![];
-        let mut right_widgets = vec![];
-        for i in 0..data.widgets_left.len() {
-            left_widgets.push(decode_widget(&data.widgets_left[i], BarWidget::Clock))
-        }
-        for i in 0..data.widgets_center.len() {
-            center_widgets.push(decode_widget(&data.widgets_center[i], BarWidget::Clock))
-        }
-        for i in 0..data.widgets_right.len() {
-            right_widgets.push(decode_widget(&data.widgets_right[i], BarWidget::Clock))
-        }
-        Configurator { //here we extract all of the data from the config file
-            theme: decode_theme(&data.theme, SelectedTheme::Light),
+        let mut errors = vec![];
+        let (data, cfg_warnings) = match get_cfg_data() {
+            Ok(loaded) => loaded,
+            Err(e) => { //start with the defaults rather than not at all, the error panel says why
+                errors.push(e);
+                (FileData::default(), vec![])
+            }
+        };
+        let profiles = match list_profiles() {
+            Ok(profiles) => profiles,
+            Err(e) => {
+                errors.push(e);
+                vec![]
+            }
+        };
+        let xkb = match read_xkb_rules() {
+            Ok(xkb) => xkb,
+            Err(e) => {
+                errors.push(e);
+                XkbRules::default()
+            }
+        };
+        let mut conf = Configurator { //here we extract all of the data from the config file
+            theme: decode_theme(data.theme),
+            cfg_version: data.version,
             current_page: Page::Main,
             wallpaper: data.wallpaper,
-            primary_key: decode_pri(&data.primary, ShortcutKey::Super),
-            secondary_key: decode_pri(&data.secondary, ShortcutKey::Shift),
-            exit_header: decode_header(&data.exit_h, BindKey::BothKey),
-            exit_key: data.exit_k,
-            launch_header: decode_header(&data.launch_h, BindKey::PrimaryKey),
-            launch_key: data.launch_k,
-            kill_header: decode_header(&data.kill_h, BindKey::BothKey),
-            kill_key: data.kill_k,
-            minimize_header: decode_header(&data.mini_h, BindKey::BothKey),
-            minimize_key: data.mini_k,
-            scratch_header: decode_header(&data.scratch_h, BindKey::PrimaryKey),
-            scratch_key: data.scratch_k,
+            primary_key: Some(data.primary),
+            secondary_key: Some(data.secondary),
+            binds: data.binds,
             unsaved: false,
             capture_next: Some(CaptureInput::NoKey),
             index: 0,
-            index_max: 4,
+            index_max: 5,
             border: data.border.clone(),
-            window_anim: decode_win_anim(&data.win_anim, WindowAnimation::None),
-            work_anim: decode_work_anim(&data.work_anim, WorkAnimation::None),
-            blur: decode_blur(&data.blur),
-            theme_set: ThemeSet {
-                light: ThemeCustom {
-                    application: theme::Palette {
-                        background: Color::from_rgb8(0xE0, 0xF5, 0xFF),
-                        text: Color::from_rgb8(0x00, 0x19, 0x36),
-                        primary: Color::from_rgb8(0x00, 0x77, 0xFF),
-                        success: Color::from_rgb8(0x00, 0xCB, 0x40),
-                        danger: Color::from_rgb8(0xFF, 0x4C, 0x00),
-                    },
-                    sidebar: ButtonStyle { 
-                        border_radius: 2.0,
-                        txt_color: Color::from_rgb8( 0x00, 0x19, 0x36),
-                        bg_color: Color::from_rgb8(0xD2, 0xF0, 0xFF),
-                        border_color: Color::from_rgb8(0, 0, 0),
-                        border_width: 0.0,
-                        shadow_offset: iced::Vector {x: 0.0, y: 0.0}
-                    },
-                    secondary: ButtonStyle {
-                        border_radius: 2.0,
-                        txt_color: Color::from_rgb8(0x00, 0x20, 0x46),
-                        bg_color: Color::from_rgb8(0xC6, 0xEC, 0xFF),
-                        border_color: Color::from_rgb8(0, 0, 0),
-                        border_width: 0.0,
-                        shadow_offset: iced::Vector {x: 0.0, y: 0.0}
-                    },
-                    list: ListStyle {
-                        txt_color: Color::from_rgb8( 0x00, 0x19, 0x36),
-                        bg_color: Color::from_rgb8(0xE0, 0xF5, 0xFF),
-                        handle_color: Color::from_rgb8( 0x00, 0x19, 0x36),
-                        border_radius: 5.0,
-                        border_width: 2.0,
-                        border_color: Color::from_rgb8( 0x00, 0x19, 0x36),
-                        menu: MenuStyle {
-                            txt_color: Color::from_rgb8( 0x00, 0x19, 0x36),
-                            bg_color: Color::from_rgb8(0xE0, 0xF5, 0xFF),
-                            border_radius: 5.0,
-                            border_width: 2.0,
-                            border_color: Color::from_rgb8( 0x00, 0x19, 0x36),
-                            sel_txt_color: Color::from_rgb8( 0x00, 0x19, 0x36),
-                            sel_bg_color: Color::from_rgb8(0x00, 0xF1, 0xD6),
-                        }
-                    }
-                },
-                dark: ThemeCustom { // TODO: set dark theme properly
-                    application: theme::Palette {
-                        background: Color::from_rgb8(0x00, 0x19, 0x36),
-                        text: Color::from_rgb8(0xE0, 0xF5, 0xFF),
-                        primary: Color::from_rgb8(0x00, 0xAB, 0xE1),
-                        success: Color::from_rgb8(0x00, 0xA9, 0x35),
-                        danger: Color::from_rgb8(0xC5, 0x3A, 0x00),
-                    },
-                    sidebar: ButtonStyle { 
-                        border_radius: 2.0,
-                        txt_color: Color::from_rgb8( 0xE0, 0xF5, 0xFF),
-                        bg_color: Color::from_rgb8(0x00, 0x20, 0x46),
-                        border_color: Color::from_rgb8(0, 0, 0),
-                        border_width: 0.0,
-                        shadow_offset: iced::Vector {x: 0.0, y: 0.0}
-                    },
-                    secondary: ButtonStyle {
-                        border_radius: 2.0,
-                        txt_color: Color::from_rgb8(0xE0, 0xF5, 0xFF),
-                        bg_color: Color::from_rgb8(0x00, 0x29, 0x58),
-                        border_color: Color::from_rgb8(0, 0, 0),
-                        border_width: 0.0,
-                        shadow_offset: iced::Vector {x: 0.0, y: 0.0}
-                    },
-                    list: ListStyle {
-                        txt_color: Color::from_rgb8(0xE0, 0xF5, 0xFF),
-                        bg_color: Color::from_rgb8(0x00, 0x29, 0x58),
-                        handle_color: Color::from_rgb8(0xE0, 0xF5, 0xFF),
-                        border_radius: 5.0,
-                        border_width: 2.0,
-                        border_color: Color::from_rgb8(0xE0, 0xF5, 0xFF),
-                        menu: MenuStyle {
-                            txt_color: Color::from_rgb8(0xE0, 0xF5, 0xFF),
-                            bg_color: Color::from_rgb8(0xE0, 0xF5, 0xFF),
-                            border_radius: 5.0,
-                            border_width: 2.0,
-                            border_color: Color::from_rgb8(0xE0, 0xF5, 0xFF),
-                            sel_txt_color: Color::from_rgb8(0xE0, 0xF5, 0xFF),
-                            sel_bg_color: Color::from_rgb8(0x00, 0xCD, 0xB6),
-                        }
-                    }
-                },
-                custom: make_custom_theme()
-            },
+            window_anim: Some(data.win_anim),
+            work_anim: Some(data.work_anim),
+            bezier_inputs: data.animations.beziers.iter().map(bezier_text).collect(),
+            bezier_names: data.animations.beziers.iter().map(|x| x.name.clone()).collect(),
+            animations: data.animations,
+            blur: data.blur,
+            theme_set: theme_set(),
             width: ShrinkValue::Full,
-            bar_left: left_widgets,
-            bar_center: center_widgets,
-            bar_right: right_widgets,
+            bar_left: data.widgets_left,
+            bar_center: data.widgets_center,
+            bar_right: data.widgets_right,
             next_widget: None,
-        }
+            workspaces: data.workspaces,
+            directions: data.directions,
+            layout: data.layout,
+            window_rules: data.window_rules,
+            clients: vec![],
+            monitors: data.monitors,
+            outputs: vec![],
+            input: data.input,
+            xkb,
+            autostart: data.autostart,
+            autostart_input: String::new(),
+            profile: data.profile,
+            profiles,
+            profile_input: String::new(),
+            cfg_warnings,
+            errors,
+            snapshot: FileData::default(),
+            history: vec![],
+            future: vec![],
+            history_field: None,
+            pending_leftover: String::new(),
+            backups: vec![],
+            backup_selected: None,
+            backup_diff: vec![],
+            preview: None,
+            preview_full: None,
+        };
+        conf.refresh_backups();
+        conf.snapshot = conf.file_data();
+        conf
     }
 }
 
@@ -220,23 +301,85 @@ enum Message { // The Message enum, used to send data to the configurator's upda
     PageChanged(Page),
     PrimaryKeyChanged(ShortcutKey),
     SecondaryKeyChanged(ShortcutKey),
-    ExitHeaderChanged(BindKey),
-    LaunchHeaderChanged(BindKey),
-    KillHeaderChanged(BindKey),
-    MiniHeaderChanged(BindKey),
-    ScratchHeaderChanged(BindKey),
+    BindHeaderChanged(usize, BindKey),
+    BindModToggled(usize, ShortcutKey),
+    BindDispatcherChanged(usize, Dispatcher),
+    BindArgChanged(usize, String),
+    AddBind,
+    RemoveBind(usize),
+    UseSuggestedKey(usize, String),
+    DirectionKeysChanged(DirectionKeys),
+    ResizeToggled(bool),
+    ResizeModChanged(ShortcutKey),
+    SubmapToggled(bool),
     KeyboardUpdate(iced::keyboard::Event),
     Capture(CaptureInput),
     Incr(IncrVal),
     Decr(IncrVal),
     ChangeWindowAnim(WindowAnimation),
     ChangeWorkAnim(WorkAnimation),
+    AnimToggled(AnimationName, bool),
+    AnimCurveChanged(AnimationName, String),
+    BezierAdd,
+    BezierRemove(usize),
+    BezierNameInput(usize, String),
+    BezierPointsInput(usize, String),
+    WorkspaceKeysChanged(WorkspaceKeys),
+    WorkspaceFollowToggled(bool),
+    WorkspaceCycleToggled(bool),
+    WorkspaceMonitorInput(u8, String),
+    LayoutEngineChanged(LayoutEngine),
+    PseudotileToggled(bool),
+    PreserveSplitToggled(bool),
+    OrientationChanged(MasterOrientation),
+    NewIsMasterToggled(bool),
+    RulesListClients,
+    RuleFromClient(usize),
+    RuleAdd,
+    RuleRemove(usize),
+    RuleActionChanged(usize, RuleAction),
+    RuleArgInput(usize, String),
+    RuleClassInput(usize, String),
+    RuleTitleInput(usize, String),
+    RuleFloatingChanged(usize, RuleState),
+    RuleXwaylandChanged(usize, RuleState),
+    MonitorsDetect,
+    MonitorsArrange,
+    MonitorModeChanged(usize, String),
+    MonitorXInput(usize, String),
+    MonitorYInput(usize, String),
+    MonitorTransformChanged(usize, Transform),
+    MonitorRemove(usize),
+    KbLayoutChanged(String),
+    KbVariantChanged(String),
+    NaturalScrollToggled(bool),
+    TouchpadScrollToggled(bool),
+    TapToClickToggled(bool),
+    FollowMouseChanged(FollowMouse),
     BlurToggled(bool),
     WindowUpdate(iced::window::Event),
     AwaitDestination(BarWidget),
     PushWidget(WidgetBank),
     RemoveWidget(WidgetBank),
     WallpaperPrompt,
+    AutostartInput(String),
+    AutostartAdd,
+    AutostartToggle(usize),
+    AutostartRemove(usize),
+    AutostartUp(usize),
+    AutostartDown(usize),
+    ProfileSelected(String),
+    ProfileInput(String),
+    ProfileSaveAs,
+    ImportPrompt,
+    DismissErrors,
+    Undo,
+    Redo,
+    BackupSelected(usize),
+    BackupRestore,
+    PreviewConfirm,
+    PreviewCancel,
+    PreviewShowFile(usize),
     NoOp,
 }
 #[derive(Debug, Clone)]
@@ -244,6 +387,13 @@ enum IncrVal {
     WidthVal,
     RadiusVal,
     GapsVal,
+    WorkspaceCount,
+    MonitorScale(usize),
+    RepeatRate,
+    RepeatDelay,
+    Sensitivity,
+    Popin,
+    AnimSpeed(AnimationName),
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
@@ -251,9 +401,15 @@ pub enum Page { //page enum, used to store the currently focused page
     #[default]
     Main,
     Bind,
+    Work,
+    Layout,
+    Rules,
+    Monitors,
+    Input,
     Bar,
     Init,
-    Anim
+    Anim,
+    Restore
 }
 
 impl std::fmt::Display for Page {
@@ -264,9 +420,15 @@ impl std::fmt::Display for Page {
             match self { //respect locale preferences when pretty-printing
                 Page::Main => tr("Main Page"),
                 Page::Bind => tr("Keybindings Page"),
+                Page::Work => tr("Workspaces Page"),
+                Page::Layout => tr("Window Layout Page"),
+                Page::Rules => tr("Window Rules Page"),
+                Page::Monitors => tr("Monitors Page"),
+                Page::Input => tr("Input Page"),
                 Page::Bar => tr("Status Bar Page"),
                 Page::Init => tr("Autostart Page"),
                 Page::Anim => tr("Animations Page"),
+                Page::Restore => tr("Restore Page"),
             }
         )
     }
@@ -287,12 +449,11 @@ impl Application for Configurator {
         format!("{}{}", tr("Cuttlefish Configurator--"), self.current_page.to_string())
     }
     fn update(&mut self, message: Self::Message) -> iced::Command<Message> { //update function, parses messages
-        match message {
+        let field = message.text_field();
+        let resized = matches!(message, Message::WindowUpdate(..)); //nothing saved changes, and these come in floods
+        let command = match message {
             Message::Save => {
-                if self.unsaved {
-                    self.mk_config();
-                }
-                self.unsaved = false;
+                self.open_preview();
                 iced::Command::none()
             }
             Message::ThemeChanged(x) => {
@@ -302,23 +463,7 @@ impl Application for Configurator {
             }
             Message::PageChanged(x) => {
                 self.current_page = x;
-                match x {
-                    Page::Main => {
-                        self.index_max = 4;
-                    }
-                    Page::Bind => {
-                        self.index_max = 7;
-                    }
-                    Page::Bar => {
-                        self.index_max = 5;
-                    }
-                    Page::Init => {
-                        self.index_max = 1;
-                    }
-                    Page::Anim => {
-                        self.index_max = 8;
-                    }
-                }
+                self.index_max = self.page_max(x);
                 if self.index > self.index_max {
                     self.index = self.index_max;
                 }
@@ -334,28 +479,61 @@ impl Application for Configurator {
                 self.unsaved = true;
                 iced::Command::none()
             }
-            Message::ExitHeaderChanged(x) => {
-                self.exit_header = Some(x);
+            Message::BindHeaderChanged(i, x) => {
+                self.binds[i].header = x;
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::BindModToggled(i, x) => {
+                self.toggle_bind_mod(i, x);
+                iced::Command::none()
+            }
+            Message::BindDispatcherChanged(i, x) => {
+                self.binds[i].dispatcher = x;
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::BindArgChanged(i, x) => {
+                self.binds[i].arg = x;
                 self.unsaved = true;
                 iced::Command::none()
             }
-            Message::LaunchHeaderChanged(x) => {
-                self.launch_header = Some(x);
+            Message::UseSuggestedKey(i, x) => {
+                self.binds[i].key = x;
                 self.unsaved = true;
                 iced::Command::none()
             }
-            Message::KillHeaderChanged(x) => {
-                self.kill_header = Some(x);
+            Message::DirectionKeysChanged(x) => {
+                self.directions.keys = x;
                 self.unsaved = true;
                 iced::Command::none()
             }
-            Message::MiniHeaderChanged(x) => {
-                self.minimize_header = Some(x);
+            Message::ResizeToggled(x) => {
+                self.directions.resize = x;
                 self.unsaved = true;
                 iced::Command::none()
             }
-            Message::ScratchHeaderChanged(x) => {
-                self.scratch_header = Some(x);
+            Message::ResizeModChanged(x) => {
+                self.directions.resize_mod = x;
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::SubmapToggled(x) => {
+                self.directions.submap = x;
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::AddBind => {
+                self.push_bind();
+                iced::Command::none()
+            }
+            Message::RemoveBind(i) => {
+                self.binds.remove(i);
+                self.capture_next = Some(CaptureInput::NoKey);
+                self.index_max = self.page_max(self.current_page);
+                if self.index > self.index_max {
+                    self.index = self.index_max;
+                }
                 self.unsaved = true;
                 iced::Command::none()
             }
@@ -372,6 +550,13 @@ impl Application for Configurator {
                     IncrVal::WidthVal => self.border.width = self.border.width + 1,
                     IncrVal::RadiusVal => self.border.radius = self.border.radius + 1,
                     IncrVal::GapsVal => self.border.gaps = self.border.gaps + 1,
+                    IncrVal::WorkspaceCount => if self.workspaces.count() < self.workspaces.keys.keys().len() {self.workspaces.count = self.workspaces.count() as u8 + 1},
+                    IncrVal::MonitorScale(i) => self.step_monitor_scale(i, true),
+                    IncrVal::RepeatRate => self.step_repeat_rate(true),
+                    IncrVal::RepeatDelay => self.step_repeat_delay(true),
+                    IncrVal::Sensitivity => self.step_sensitivity(true),
+                    IncrVal::Popin => self.step_popin(true),
+                    IncrVal::AnimSpeed(name) => self.step_anim_speed(name, true),
                 }
                 self.unsaved = true;
                 iced::Command::none()
@@ -381,6 +566,13 @@ impl Application for Configurator {
                     IncrVal::WidthVal => if self.border.width > 0 {self.border.width = self.border.width - 1},
                     IncrVal::RadiusVal => if self.border.radius > 0 {self.border.radius = self.border.radius - 1},
                     IncrVal::GapsVal => if self.border.gaps > 0 {self.border.gaps = self.border.gaps - 1},
+                    IncrVal::WorkspaceCount => if self.workspaces.count() > 1 {self.workspaces.count = self.workspaces.count() as u8 - 1},
+                    IncrVal::MonitorScale(i) => self.step_monitor_scale(i, false),
+                    IncrVal::RepeatRate => self.step_repeat_rate(false),
+                    IncrVal::RepeatDelay => self.step_repeat_delay(false),
+                    IncrVal::Sensitivity => self.step_sensitivity(false),
+                    IncrVal::Popin => self.step_popin(false),
+                    IncrVal::AnimSpeed(name) => self.step_anim_speed(name, false),
                 }
                 self.unsaved = true;
                 iced::Command::none()
@@ -395,6 +587,181 @@ impl Application for Configurator {
                 self.unsaved = true;
                 iced::Command::none()
             }
+            Message::AnimToggled(name, x) => {
+                self.set_anim_enabled(name, x);
+                iced::Command::none()
+            }
+            Message::AnimCurveChanged(name, x) => {
+                self.set_anim_curve(name, x);
+                iced::Command::none()
+            }
+            Message::BezierAdd => {
+                self.add_bezier();
+                iced::Command::none()
+            }
+            Message::BezierRemove(i) => {
+                self.remove_bezier(i);
+                iced::Command::none()
+            }
+            Message::BezierNameInput(i, x) => {
+                self.rename_bezier(i, x);
+                iced::Command::none()
+            }
+            Message::BezierPointsInput(i, x) => {
+                self.set_bezier_points(i, x);
+                iced::Command::none()
+            }
+            Message::WorkspaceKeysChanged(x) => {
+                self.workspaces.keys = x;
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::WorkspaceFollowToggled(x) => {
+                self.workspaces.follow = x;
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::WorkspaceCycleToggled(x) => {
+                self.workspaces.cycle = x;
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::WorkspaceMonitorInput(ws, x) => {
+                self.workspaces.set_monitor(ws, x);
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::LayoutEngineChanged(x) => {
+                self.layout.engine = x;
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::PseudotileToggled(x) => {
+                self.layout.pseudotile = x;
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::PreserveSplitToggled(x) => {
+                self.layout.preserve_split = x;
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::OrientationChanged(x) => {
+                self.layout.orientation = x;
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::NewIsMasterToggled(x) => {
+                self.layout.new_is_master = x;
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::RulesListClients => {
+                self.list_clients();
+                iced::Command::none()
+            }
+            Message::RuleFromClient(i) => {
+                self.add_rule_from_client(i);
+                iced::Command::none()
+            }
+            Message::RuleAdd => {
+                self.add_rule();
+                iced::Command::none()
+            }
+            Message::RuleRemove(i) => {
+                self.window_rules.remove(i);
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::RuleActionChanged(i, x) => {
+                self.window_rules[i].action = x;
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::RuleArgInput(i, x) => {
+                self.window_rules[i].arg = x;
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::RuleClassInput(i, x) => {
+                self.window_rules[i].class = x;
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::RuleTitleInput(i, x) => {
+                self.window_rules[i].title = x;
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::RuleFloatingChanged(i, x) => {
+                self.window_rules[i].floating = x;
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::RuleXwaylandChanged(i, x) => {
+                self.window_rules[i].xwayland = x;
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::MonitorsDetect => {
+                self.detect_monitors();
+                iced::Command::none()
+            }
+            Message::MonitorsArrange => {
+                self.arrange_monitors();
+                iced::Command::none()
+            }
+            Message::MonitorModeChanged(i, x) => {
+                self.set_monitor_mode(i, &x);
+                iced::Command::none()
+            }
+            Message::MonitorXInput(i, x) => {
+                self.set_monitor_position(i, true, &x);
+                iced::Command::none()
+            }
+            Message::MonitorYInput(i, x) => {
+                self.set_monitor_position(i, false, &x);
+                iced::Command::none()
+            }
+            Message::MonitorTransformChanged(i, x) => {
+                self.monitors[i].transform = x;
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::MonitorRemove(i) => {
+                self.monitors.remove(i);
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::KbLayoutChanged(x) => {
+                self.set_kb_layout(x);
+                iced::Command::none()
+            }
+            Message::KbVariantChanged(x) => {
+                self.input.kb_variant = x;
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::NaturalScrollToggled(x) => {
+                self.input.natural_scroll = x;
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::TouchpadScrollToggled(x) => {
+                self.input.touchpad_natural_scroll = x;
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::TapToClickToggled(x) => {
+                self.input.tap_to_click = x;
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::FollowMouseChanged(x) => {
+                self.input.follow_mouse = x;
+                self.unsaved = true;
+                iced::Command::none()
+            }
             Message::BlurToggled(x) => {
                 self.blur = x;
                 self.unsaved = true;
@@ -512,10 +879,98 @@ impl Application for Configurator {
                 };
                 iced::Command::none()
             }
+            Message::AutostartInput(x) => {
+                self.autostart_input = x;
+                iced::Command::none()
+            }
+            Message::AutostartAdd => {
+                self.push_autostart();
+                iced::Command::none()
+            }
+            Message::AutostartToggle(i) => {
+                self.autostart[i].enabled = !self.autostart[i].enabled;
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::AutostartRemove(i) => {
+                self.autostart.remove(i);
+                self.index_max = self.page_max(self.current_page);
+                if self.index > self.index_max {
+                    self.index = self.index_max;
+                }
+                self.unsaved = true;
+                iced::Command::none()
+            }
+            Message::AutostartUp(i) => {
+                if i > 0 {
+                    self.autostart.swap(i, i - 1);
+                    self.unsaved = true;
+                }
+                iced::Command::none()
+            }
+            Message::AutostartDown(i) => {
+                if i + 1 < self.autostart.len() {
+                    self.autostart.swap(i, i + 1);
+                    self.unsaved = true;
+                }
+                iced::Command::none()
+            }
+            Message::ProfileSelected(x) => {
+                self.switch_profile(&x);
+                iced::Command::none()
+            }
+            Message::ProfileInput(x) => {
+                self.profile_input = x;
+                iced::Command::none()
+            }
+            Message::ProfileSaveAs => {
+                self.save_profile_as();
+                iced::Command::none()
+            }
+            Message::ImportPrompt => {
+                self.import_prompt();
+                iced::Command::none()
+            }
+            Message::DismissErrors => {
+                self.errors.clear();
+                iced::Command::none()
+            }
+            Message::Undo => {
+                self.undo();
+                iced::Command::none()
+            }
+            Message::Redo => {
+                self.redo();
+                iced::Command::none()
+            }
+            Message::BackupSelected(i) => {
+                self.select_backup(i);
+                iced::Command::none()
+            }
+            Message::BackupRestore => {
+                self.restore_selected();
+                iced::Command::none()
+            }
+            Message::PreviewConfirm => {
+                self.confirm_preview();
+                iced::Command::none()
+            }
+            Message::PreviewCancel => {
+                self.cancel_preview();
+                iced::Command::none()
+            }
+            Message::PreviewShowFile(i) => {
+                self.preview_full = if self.preview_full == Some(i) {None} else {Some(i)};
+                iced::Command::none()
+            }
             Message::NoOp => {
                 iced::Command::none()
             }
+        };
+        if !resized {
+            self.record_history(field);
         }
+        command
     }
     fn view(&self) -> iced::Element<'_, Self::Message> {
 
@@ -528,9 +983,15 @@ impl Application for Configurator {
 
         let main_txt = Text::new(Page::Main.to_string());
         let bind_txt = Text::new(Page::Bind.to_string());
+        let work_txt = Text::new(Page::Work.to_string());
+        let layout_txt = Text::new(Page::Layout.to_string());
+        let rules_txt = Text::new(Page::Rules.to_string());
+        let monitors_txt = Text::new(Page::Monitors.to_string());
+        let input_txt = Text::new(Page::Input.to_string());
         let bar_txt = Text::new(Page::Bar.to_string());
         let init_txt = Text::new(Page::Init.to_string());
         let anim_txt = Text::new(Page::Anim.to_string());
+        let restore_txt = Text::new(Page::Restore.to_string());
         let mut page_main = Button::new(main_txt)
             .on_press(Message::PageChanged(Page::Main))
             .width(SIDEBAR_WIDTH)
@@ -539,6 +1000,26 @@ impl Application for Configurator {
             .on_press(Message::PageChanged(Page::Bind))
             .width(SIDEBAR_WIDTH)
             .style(style.sidebar.mk_theme());
+        let mut page_work = Button::new(work_txt)
+            .on_press(Message::PageChanged(Page::Work))
+            .width(SIDEBAR_WIDTH)
+            .style(style.sidebar.mk_theme());
+        let mut page_layout = Button::new(layout_txt)
+            .on_press(Message::PageChanged(Page::Layout))
+            .width(SIDEBAR_WIDTH)
+            .style(style.sidebar.mk_theme());
+        let mut page_rules = Button::new(rules_txt)
+            .on_press(Message::PageChanged(Page::Rules))
+            .width(SIDEBAR_WIDTH)
+            .style(style.sidebar.mk_theme());
+        let mut page_monitors = Button::new(monitors_txt)
+            .on_press(Message::PageChanged(Page::Monitors))
+            .width(SIDEBAR_WIDTH)
+            .style(style.sidebar.mk_theme());
+        let mut page_input = Button::new(input_txt)
+            .on_press(Message::PageChanged(Page::Input))
+            .width(SIDEBAR_WIDTH)
+            .style(style.sidebar.mk_theme());
         let mut page_bar = Button::new(bar_txt)
             .on_press(Message::PageChanged(Page::Bar))
             .width(SIDEBAR_WIDTH)
@@ -551,39 +1032,60 @@ impl Application for Configurator {
             .on_press(Message::PageChanged(Page::Anim))
             .width(SIDEBAR_WIDTH)
             .style(style.sidebar.mk_theme());
+        let mut page_restore = Button::new(restore_txt)
+            .on_press(Message::PageChanged(Page::Restore))
+            .width(SIDEBAR_WIDTH)
+            .style(style.sidebar.mk_theme());
         let page_cap = Button::new("").width(SIDEBAR_WIDTH).height(10000).style(style.sidebar.mk_theme()).on_press(Message::NoOp);
         let page_label = Text::new(tr("Available Pages"));
         match self.current_page {
             Page::Main => page_main = page_main.style(style.secondary.mk_theme()),
             Page::Bind => page_bind = page_bind.style(style.secondary.mk_theme()),
+            Page::Work => page_work = page_work.style(style.secondary.mk_theme()),
+            Page::Layout => page_layout = page_layout.style(style.secondary.mk_theme()),
+            Page::Rules => page_rules = page_rules.style(style.secondary.mk_theme()),
+            Page::Monitors => page_monitors = page_monitors.style(style.secondary.mk_theme()),
+            Page::Input => page_input = page_input.style(style.secondary.mk_theme()),
             Page::Bar => page_bar = page_bar.style(style.secondary.mk_theme()),
             Page::Init => page_init = page_init.style(style.secondary.mk_theme()),
             Page::Anim => page_anim = page_anim.style(style.secondary.mk_theme()),
+            Page::Restore => page_restore = page_restore.style(style.secondary.mk_theme()),
         }
         let page_col = Column::new()
             .push(page_label)
             .push(page_main)
             .push(page_bind)
+            .push(page_work)
+            .push(page_layout)
+            .push(page_rules)
+            .push(page_monitors)
+            .push(page_input)
             .push(page_anim)
             .push(page_bar)
             .push(page_init)
+            .push(page_restore)
             .push(page_cap)
             .align_items(Alignment::Start);
 
         let save_txt = Text::new(tr("Save"));
         let saved_txt = Text::new(tr("Saved!"));
-        let save = match (self.unsaved, self.index == self.index_max) {
-            (true, true) => {
-                Button::new(save_txt).on_press(Message::Save).style(theme::Button::Positive)
-            }
-            (true, false) => {
-                Button::new(save_txt).on_press(Message::Save)
-            }
-            (false, true) => {
-                Button::new(saved_txt).on_press(Message::Save).style(theme::Button::Positive)
-            }
-            (false, false) => {
-                Button::new(saved_txt).on_press(Message::Save).style(style.secondary.mk_theme())
+        let conflict_txt = Text::new(tr("Resolve Keybinding Conflicts"));
+        let save = if self.conflicts().len() > 0 {
+            Button::new(conflict_txt).on_press(Message::Save).style(theme::Button::Destructive)
+        } else {
+            match (self.unsaved, self.index == self.index_max) {
+                (true, true) => {
+                    Button::new(save_txt).on_press(Message::Save).style(theme::Button::Positive)
+                }
+                (true, false) => {
+                    Button::new(save_txt).on_press(Message::Save)
+                }
+                (false, true) => {
+                    Button::new(saved_txt).on_press(Message::Save).style(theme::Button::Positive)
+                }
+                (false, false) => {
+                    Button::new(saved_txt).on_press(Message::Save).style(style.secondary.mk_theme())
+                }
             }
         };
         /* 
@@ -596,12 +1098,29 @@ impl Application for Configurator {
             .style(style.secondary.mk_theme());
         }
         */
+        let mut undo = Button::new(Text::new(tr("Undo")));
+        if self.history.len() > 0 {
+            undo = undo.on_press(Message::Undo);
+        }
+        let mut redo = Button::new(Text::new(tr("Redo")));
+        if self.future.len() > 0 {
+            redo = redo.on_press(Message::Redo);
+        }
         let save_row = Row::new()
+            .push(undo)
+            .push(redo)
             .push(save)
+            .spacing(10)
             .align_items(Alignment::Center);
         
         let mut settings = Column::new().spacing(10);
-
+        let mut error_col = Column::new().spacing(5);
+        if self.errors.len() > 0 {
+            for error in &self.errors {
+                error_col = error_col.push(Text::new(error.to_string()).style(TextStyle {color: style.application.danger}.mk_theme()));
+            }
+            error_col = error_col.push(Button::new(Text::new(tr("Dismiss"))).on_press(Message::DismissErrors));
+        }
 
         match self.current_page {
             Page::Main => {
@@ -610,21 +1129,46 @@ impl Application for Configurator {
             Page::Bind => {
                 settings = self.bind_page(style);
             }
+            Page::Work => {
+                settings = self.workspace_page(style);
+            }
+            Page::Layout => {
+                settings = self.layout_page(style);
+            }
+            Page::Rules => {
+                settings = self.rules_page(style);
+            }
+            Page::Monitors => {
+                settings = self.monitor_page(style);
+            }
+            Page::Input => {
+                settings = self.input_page(style);
+            }
             Page::Bar => {
                 settings = self.bar_page(style);
             }
             Page::Init => {
-                
+                settings = self.init_page(style);
             }
             Page::Anim => {
                 settings = self.anim_page(style);
             }
+            Page::Restore => {
+                settings = self.restore_page(style);
+            }
+        }
+        if self.preview.is_some() { //covers whichever page the save came from
+            settings = self.preview_page(style);
         }
         let test_rule = Rule::vertical(1);
         let scroll = Scrollable::new(settings);
-        let col = Column::new()
-            .push(scroll)
-            .push(save_row)
+        let mut col = Column::new()
+            .push(error_col)
+            .push(scroll);
+        if self.preview.is_none() { //the preview has its own buttons
+            col = col.push(save_row);
+        }
+        let col = col
             .width(Length::Fill)
             .align_items(Alignment::Start)
             .spacing(10);
@@ -648,8 +1192,11 @@ impl Application for Configurator {
     }
     fn subscription(&self) -> iced::Subscription<Message> {
         iced::subscription::events_with(
-            |event, _| {
+            |event, status| {
                 if let iced::Event::Keyboard(keyboard_event) = event {
+                    if status == iced::event::Status::Captured { //keys typed into a text box shouldn't trigger shortcuts
+                        return None;
+                    }
                     Some(Message::KeyboardUpdate(keyboard_event))
                 } else if let iced::Event::Window(window_event) = event{
                     Some(Message::WindowUpdate(window_event))
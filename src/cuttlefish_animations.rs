@@ -0,0 +1,99 @@
+use crate::lib_cfg::{AnimationName, Bezier};
+use crate::Configurator;
+
+//The animations page past the presets, speeds, curves and the popin size
+
+const SPEED_STEP: f64 = 0.5;
+const POPIN_STEP: u32 = 5;
+
+pub fn bezier_text(bezier: &Bezier) -> String { //what the points box shows, the same order bezier= takes
+    format!("{}, {}, {}, {}", bezier.x0, bezier.y0, bezier.x1, bezier.y1)
+}
+pub fn parse_bezier_points(text: &str) -> Option<[f64; 4]> {
+    let parts: Vec<&str> = text.split(',').map(|x| x.trim()).collect();
+    if parts.len() != 4 {
+        return None;
+    }
+    let mut points = [0.0; 4];
+    for i in 0..4 {
+        points[i] = parts[i].parse().ok()?;
+    }
+    Some(points)
+}
+
+impl Configurator {
+    pub fn step_popin(&mut self, up: bool) { //below 5% windows seem to appear from nowhere
+        let popin = self.animations.popin;
+        self.animations.popin = if up {(popin + POPIN_STEP).min(100)} else {popin.saturating_sub(POPIN_STEP).max(POPIN_STEP)};
+        self.unsaved = true;
+    }
+    pub fn step_anim_speed(&mut self, name: AnimationName, up: bool) { //snaps to the next half, an imported speed may be in between
+        let entry = self.animations.entry_mut(name);
+        let steps = entry.speed / SPEED_STEP;
+        let next = if up {steps.floor() + 1.0} else {steps.ceil() - 1.0};
+        if next >= 1.0 && next * SPEED_STEP <= 20.0 {
+            entry.speed = next * SPEED_STEP;
+            self.unsaved = true;
+        }
+    }
+    pub fn set_anim_enabled(&mut self, name: AnimationName, enabled: bool) {
+        if name.switchable() && !name.preset_off(self.window_anim.unwrap(), self.work_anim.unwrap()) {
+            self.animations.entry_mut(name).enabled = enabled;
+            self.unsaved = true;
+        }
+    }
+    pub fn set_anim_curve(&mut self, name: AnimationName, curve: String) {
+        self.animations.entry_mut(name).curve = curve;
+        self.unsaved = true;
+    }
+    pub fn add_bezier(&mut self) { //starts out as the usual ease curve, left out of hyprland.conf until it has a name
+        let bezier = Bezier {
+            name: String::new(),
+            x0: 0.25,
+            y0: 0.1,
+            x1: 0.25,
+            y1: 1.0,
+        };
+        self.bezier_inputs.push(bezier_text(&bezier));
+        self.bezier_names.push(String::new());
+        self.animations.beziers.push(bezier);
+        self.unsaved = true;
+    }
+    pub fn remove_bezier(&mut self, i: usize) { //what used it goes back to the default curve
+        for j in self.animations.curve_users(i) {
+            self.animations.entries[j].curve = "default".to_string();
+        }
+        self.animations.beziers.remove(i);
+        self.bezier_inputs.remove(i);
+        self.bezier_names.remove(i);
+        self.unsaved = true;
+    }
+    pub fn rename_bezier(&mut self, i: usize, name: String) { //the animations using it follow along, a name that is taken waits in the box
+        if !self.animations.name_taken(i, &name) {
+            let users = self.animations.curve_users(i);
+            let name = name.trim().to_string();
+            for entry in &mut self.animations.entries { //still pointing at a curve deleted outside of cuttlefish, they were drawn with default
+                if entry.curve.trim() == name {
+                    entry.curve = "default".to_string();
+                }
+            }
+            for j in users {
+                self.animations.entries[j].curve = if name.len() > 0 {name.clone()} else {"default".to_string()};
+            }
+            self.animations.beziers[i].name = name;
+            self.unsaved = true;
+        }
+        self.bezier_names[i] = name;
+    }
+    pub fn set_bezier_points(&mut self, i: usize, text: String) { //kept as typed, the curve only changes once all four numbers are there
+        if let Some(points) = parse_bezier_points(&text) {
+            let bezier = &mut self.animations.beziers[i];
+            bezier.x0 = points[0];
+            bezier.y0 = points[1];
+            bezier.x1 = points[2];
+            bezier.y1 = points[3];
+            self.unsaved = true;
+        }
+        self.bezier_inputs[i] = text;
+    }
+}
@@ -0,0 +1,195 @@
+use std::io::Write;
+use gettextrs::gettext as tr;
+use crate::lib_cfg::{decode_theme, find_conflicts, get_cfg_data, get_home, load_profile, peek_cfg_data, BindConflict, CfgError, CfgWarning, FileData};
+use crate::lib_import::{import_hyprland, usercfg_with};
+use crate::lib_render::{hyprland_path, render_hyprland, render_waybar, render_waybar_style, RenderInput};
+use crate::cuttlefish_save_helper::{palette_of, write_config};
+use crate::theme_set;
+
+//Headless entry points, for scripts and sessions without a display
+
+const EXIT_OK: i32 = 0;
+const EXIT_BAD_CFG: i32 = 1;
+const EXIT_USAGE: i32 = 2;
+
+pub fn run_cli(args: Vec<String>) -> i32 {
+    let args: Vec<&str> = args.iter().map(|x| x.as_str()).collect();
+    match args.as_slice() {
+        ["apply"] => apply(),
+        ["--profile", name] | ["apply", "--profile", name] => apply_profile(name),
+        ["validate"] => validate(),
+        ["print", target] => print(target),
+        ["import"] => import(None),
+        ["import", path] => import(Some(path)),
+        ["help"] | ["-h"] | ["--help"] => {
+            println!("{}", usage());
+            EXIT_OK
+        }
+        _ => {
+            eprintln!("{}", usage());
+            EXIT_USAGE
+        }
+    }
+}
+fn usage() -> String {
+    format!("{}\n  cuttlefish                   {}\n  cuttlefish apply             {}\n  cuttlefish --profile <name>  {}\n  cuttlefish print <target>    {}\n  cuttlefish validate          {}\n  cuttlefish import [path]     {}",
+        tr("Usage:"),
+        tr("open the configurator"),
+        tr("regenerate and reload every config from cfg.toml"),
+        tr("switch to a saved profile and apply it"),
+        tr("write a generated config to stdout, target is hyprland, waybar or waybar-style"),
+        tr("check cfg.toml for values that can't be read"),
+        tr("take over a hand-written hyprland.conf, the current one by default, and apply it")
+    )
+}
+fn load(read_only: bool) -> Option<(FileData, Vec<CfgWarning>)> { //reports the problems itself so that every subcommand fails the same way
+    let loaded = if read_only {peek_cfg_data()} else {get_cfg_data()};
+    match loaded {
+        Ok(loaded) => Some(loaded),
+        Err(e) => {
+            eprintln!("{e}");
+            None
+        }
+    }
+}
+fn conflicts(data: &FileData) -> Vec<BindConflict> {
+    find_conflicts(&data.binds, Some(data.primary), Some(data.secondary), &data.workspaces, &data.directions, &data.layout)
+}
+fn render_input(data: FileData, usercfg: Option<String>) -> Result<RenderInput, CfgError> { //the same colors the gui would write, without starting it
+    Ok(RenderInput {
+        palette: palette_of(&decode_theme(data.theme), &theme_set()),
+        data,
+        home: get_home()?,
+        usercfg,
+    })
+}
+fn validate() -> i32 {
+    let (data, warnings) = match load(true) {
+        Some(loaded) => loaded,
+        None => return EXIT_BAD_CFG
+    };
+    let mut problems: Vec<String> = warnings.iter().map(|x| x.to_string()).collect();
+    for conflict in conflicts(&data) {
+        problems.push(match (conflict.index, conflict.other) {
+            (Some(index), Some(other)) => format!("binds[{index}]: {} binds[{other}]", tr("uses the same keys as")),
+            (Some(index), None) => format!("binds[{index}]: {}", tr("uses keys that are already taken by a built-in binding")),
+            (None, _) => conflict.built_in_text(),
+        });
+    }
+    for problem in &problems {
+        eprintln!("{problem}");
+    }
+    if problems.len() > 0 {
+        EXIT_BAD_CFG
+    } else {
+        println!("{}", tr("cfg.toml is valid"));
+        EXIT_OK
+    }
+}
+fn apply() -> i32 {
+    match load(false) {
+        Some((data, warnings)) => {
+            for warning in warnings { //not fatal, the same defaults the gui would pick are used
+                eprintln!("{warning}");
+            }
+            apply_data(data, None)
+        }
+        None => EXIT_BAD_CFG
+    }
+}
+fn apply_profile(name: &str) -> i32 {
+    let data = match load_profile(name) {
+        Ok((data, warnings)) => {
+            for warning in warnings {
+                eprintln!("{warning}");
+            }
+            data
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            return EXIT_BAD_CFG;
+        }
+    };
+    apply_data(data, None)
+}
+fn apply_data(data: FileData, usercfg: Option<String>) -> i32 {
+    if conflicts(&data).len() > 0 { //same rule as the save button
+        eprintln!("{}", tr("Resolve Keybinding Conflicts"));
+        return EXIT_BAD_CFG;
+    }
+    let errors = match render_input(data, usercfg) {
+        Ok(input) => write_config(&input),
+        Err(e) => vec![e]
+    };
+    for error in &errors {
+        eprintln!("{error}");
+    }
+    if errors.len() > 0 {
+        EXIT_BAD_CFG
+    } else {
+        EXIT_OK
+    }
+}
+fn import(path: Option<&str>) -> i32 {
+    let data = match load(false) {
+        Some((data, _)) => data,
+        None => return EXIT_BAD_CFG
+    };
+    let home = match get_home() {
+        Ok(home) => home,
+        Err(e) => {
+            eprintln!("{e}");
+            return EXIT_BAD_CFG;
+        }
+    };
+    let path = path.map(|x| x.to_string()).unwrap_or(hyprland_path(&home));
+    let imported = match import_hyprland(&path, data) {
+        Ok(imported) => imported,
+        Err(e) => {
+            eprintln!("{e}");
+            return EXIT_BAD_CFG;
+        }
+    };
+    for warning in &imported.warnings {
+        eprintln!("{warning}");
+    }
+    let usercfg = match usercfg_with(&home, &imported.leftover) { //written with the rest, so a refused apply leaves it alone
+        Ok(usercfg) => usercfg,
+        Err(e) => {
+            eprintln!("{e}");
+            return EXIT_BAD_CFG;
+        }
+    };
+    apply_data(imported.data, usercfg)
+}
+fn print(target: &str) -> i32 {
+    let data = match load(true) {
+        Some((data, warnings)) => {
+            for warning in warnings { //not fatal, the same defaults the gui would pick are used
+                eprintln!("{warning}");
+            }
+            data
+        }
+        None => return EXIT_BAD_CFG
+    };
+    let input = match render_input(data, None) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{e}");
+            return EXIT_BAD_CFG;
+        }
+    };
+    let output = match target {
+        "hyprland" => render_hyprland(&input),
+        "waybar" => render_waybar(&input),
+        "waybar-style" => render_waybar_style(&input),
+        _ => {
+            eprintln!("{}", usage());
+            return EXIT_USAGE;
+        }
+    };
+    match std::io::stdout().write_all(output.as_bytes()) {
+        Ok(..) => EXIT_OK,
+        Err(..) => EXIT_BAD_CFG
+    }
+}
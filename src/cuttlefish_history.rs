@@ -0,0 +1,61 @@
+use crate::{Configurator, Message};
+
+//Undo and redo, kept as whole snapshots of the settings so that every kind of change is covered
+
+const HISTORY_LIMIT: usize = 100;
+
+pub type TextField = (&'static str, usize); //which text box a message types into, and on which row
+
+impl Message {
+    pub fn text_field(&self) -> Option<TextField> { //typing into the same box again extends the last undo step instead of adding one per key
+        match self {
+            Message::BindArgChanged(i, _) => Some(("bind_arg", *i)),
+            Message::WorkspaceMonitorInput(ws, _) => Some(("workspace_monitor", *ws as usize)),
+            Message::RuleArgInput(i, _) => Some(("rule_arg", *i)),
+            Message::RuleClassInput(i, _) => Some(("rule_class", *i)),
+            Message::RuleTitleInput(i, _) => Some(("rule_title", *i)),
+            Message::MonitorXInput(i, _) => Some(("monitor_x", *i)),
+            Message::MonitorYInput(i, _) => Some(("monitor_y", *i)),
+            Message::KbLayoutChanged(_) => Some(("kb_layout", 0)),
+            Message::KbVariantChanged(_) => Some(("kb_variant", 0)),
+            Message::BezierNameInput(i, _) => Some(("bezier_name", *i)),
+            Message::BezierPointsInput(i, _) => Some(("bezier_points", *i)),
+            _ => None
+        }
+    }
+}
+
+impl Configurator {
+    pub fn record_history(&mut self, field: Option<TextField>) { //run after every update, remembers the old settings whenever they changed
+        let current = self.file_data();
+        if current != self.snapshot {
+            let previous = std::mem::replace(&mut self.snapshot, current);
+            if field.is_none() || field != self.history_field || self.history.len() == 0 { //the step before this one already holds the settings from before the typing began
+                self.history.push(previous);
+                if self.history.len() > HISTORY_LIMIT {
+                    self.history.remove(0);
+                }
+            }
+            self.history_field = field;
+            self.future.clear();
+        }
+    }
+    pub fn undo(&mut self) {
+        if let Some(previous) = self.history.pop() {
+            let current = std::mem::replace(&mut self.snapshot, previous.clone());
+            self.future.push(current);
+            self.history_field = None;
+            self.load_file_data(previous);
+            self.unsaved = true;
+        }
+    }
+    pub fn redo(&mut self) {
+        if let Some(next) = self.future.pop() {
+            let current = std::mem::replace(&mut self.snapshot, next.clone());
+            self.history.push(current);
+            self.history_field = None;
+            self.load_file_data(next);
+            self.unsaved = true;
+        }
+    }
+}
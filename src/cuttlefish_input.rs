@@ -0,0 +1,62 @@
+use crate::lib_cfg::FollowMouse;
+use crate::Configurator;
+
+//The input page, keyboard layout and repeat, pointer and touchpad
+
+const RATE_STEP: i32 = 5;
+const DELAY_STEP: i32 = 50;
+
+impl Configurator {
+    pub fn set_kb_layout(&mut self, layout: String) { //variants only make sense for their own layout
+        if self.xkb.variants_of(layout.trim()).iter().all(|x| x.code != self.input.kb_variant) {
+            self.input.kb_variant = String::new();
+        }
+        self.input.kb_layout = layout;
+        self.unsaved = true;
+    }
+    pub fn cycle_kb_layout(&mut self, up: bool) { //through the layouts xkb lists, a typed one starts from the top
+        let count = self.xkb.layouts.len();
+        if count == 0 {
+            return;
+        }
+        let next = match self.xkb.layouts.iter().position(|x| x.code == self.input.kb_layout) {
+            Some(pos) if up => (pos + 1) % count,
+            Some(pos) => (pos + count - 1) % count,
+            None => 0
+        };
+        self.set_kb_layout(self.xkb.layouts[next].code.clone());
+    }
+    pub fn cycle_kb_variant(&mut self, up: bool) { //the layout's default comes before its first variant
+        let variants = self.xkb.variants_of(&self.input.kb_layout);
+        let count = variants.len() + 1;
+        let pos = match variants.iter().position(|x| x.code == self.input.kb_variant) {
+            Some(pos) => pos + 1,
+            None => 0
+        };
+        let next = if up {(pos + 1) % count} else {(pos + count - 1) % count};
+        self.input.kb_variant = if next == 0 {String::new()} else {variants[next - 1].code.clone()};
+        self.unsaved = true;
+    }
+    pub fn step_repeat_rate(&mut self, up: bool) { //hyprland takes 1 to 100 repeats per second
+        let next = if up {self.input.repeat_rate + RATE_STEP} else {self.input.repeat_rate - RATE_STEP};
+        self.input.repeat_rate = next.clamp(1, 100);
+        self.unsaved = true;
+    }
+    pub fn step_repeat_delay(&mut self, up: bool) {
+        let next = if up {self.input.repeat_delay + DELAY_STEP} else {self.input.repeat_delay - DELAY_STEP};
+        self.input.repeat_delay = next.clamp(100, 2000);
+        self.unsaved = true;
+    }
+    pub fn step_sensitivity(&mut self, up: bool) { //in tenths, so repeated steps don't pile up rounding errors
+        let tenths = (self.input.sensitivity * 10.0).round();
+        let next = if up {tenths + 1.0} else {tenths - 1.0};
+        self.input.sensitivity = next.clamp(-10.0, 10.0) / 10.0;
+        self.unsaved = true;
+    }
+    pub fn cycle_follow_mouse(&mut self, up: bool) {
+        let count = FollowMouse::ALL.len();
+        let pos = FollowMouse::ALL.iter().position(|x| *x == self.input.follow_mouse).unwrap();
+        self.input.follow_mouse = FollowMouse::ALL[if up {(pos + 1) % count} else {(pos + count - 1) % count}];
+        self.unsaved = true;
+    }
+}
@@ -0,0 +1,59 @@
+use crate::lib_monitors::{arrange, monitor_from_output, parse_mode, read_outputs};
+use crate::Configurator;
+
+//The monitors page, starts from what hyprctl reports and keeps the changes in cfg.toml
+
+const SCALE_STEP: f64 = 0.25;
+
+impl Configurator {
+    pub fn detect_monitors(&mut self) { //outputs we have no settings for yet start out as they are now
+        match read_outputs() {
+            Ok(outputs) => {
+                for output in &outputs {
+                    if !self.monitors.iter().any(|x| x.name == output.name) {
+                        self.monitors.push(monitor_from_output(output));
+                        self.unsaved = true;
+                    }
+                }
+                self.outputs = outputs;
+            }
+            Err(e) => self.errors.push(e)
+        }
+    }
+    pub fn set_monitor_mode(&mut self, i: usize, mode: &str) {
+        if let Some((width, height, refresh)) = parse_mode(mode) {
+            self.monitors[i].width = width;
+            self.monitors[i].height = height;
+            self.monitors[i].refresh = refresh;
+            self.unsaved = true;
+        }
+    }
+    pub fn set_monitor_position(&mut self, i: usize, horizontal: bool, text: &str) { //a lone minus sign counts as 0 until a number follows
+        let text = text.trim();
+        let value = if text.len() == 0 || text == "-" {
+            Some(0)
+        } else {
+            text.parse().ok()
+        };
+        if let Some(value) = value {
+            if horizontal {
+                self.monitors[i].x = value;
+            } else {
+                self.monitors[i].y = value;
+            }
+            self.unsaved = true;
+        }
+    }
+    pub fn step_monitor_scale(&mut self, i: usize, up: bool) { //snaps to the next quarter, hyprctl may have reported something in between
+        let steps = self.monitors[i].scale / SCALE_STEP;
+        let next = if up {steps.floor() + 1.0} else {steps.ceil() - 1.0};
+        if next >= 1.0 {
+            self.monitors[i].scale = next * SCALE_STEP;
+            self.unsaved = true;
+        }
+    }
+    pub fn arrange_monitors(&mut self) {
+        arrange(&mut self.monitors);
+        self.unsaved = true;
+    }
+}
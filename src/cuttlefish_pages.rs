@@ -1,8 +1,12 @@
 use gettextrs::gettext as tr;
-use iced::widget::{Column, Text, pick_list, Button, Row};
+use iced::widget::{Column, Text, pick_list, Button, Row, text_input};
 use oceania_style::{SelectedTheme, TextStyle, ThemeCustom};
 
-use crate::{Configurator, Message, lib_cfg::{ShortcutKey, BindKey, BarWidget, WorkAnimation, WindowAnimation}, ShrinkValue, CaptureInput, WidgetBank, IncrVal};
+use crate::{Configurator, Message, lib_cfg::{ShortcutKey, BindKey, BarWidget, WorkAnimation, WindowAnimation, Dispatcher, WorkspaceKeys, DirectionKeys, Transform, FollowMouse, LayoutEngine, MasterOrientation, PSEUDO_KEY, SPLIT_KEY, SWAP_MASTER_KEY, RuleAction, RuleState, AnimationName, rip_window_rule, rip_shortcut, rip_key}, ShrinkValue, CaptureInput, WidgetBank, IncrVal};
+use crate::lib_backup::format_stamp;
+use crate::lib_diff::{DiffLine, has_changes};
+use crate::lib_monitors::mode_matches;
+use crate::cuttlefish_animations::parse_bezier_points;
 
 
 
@@ -64,12 +68,34 @@ impl Configurator {
         }
         let mut wallpaper_label = Text::new(tr("The Wallpaper to use"));
         let wallpaper_select = Button::new(Text::new(self.wallpaper.clone())).on_press(Message::WallpaperPrompt);
+        let mut profile_label = Text::new(tr("Profile"));
+        let active_profile = if self.profile.len() > 0 {
+            Some(self.profile.clone())
+        } else {
+            None
+        };
+        let profile_pick = pick_list(
+            &self.profiles[..],
+            active_profile,
+            Message::ProfileSelected,
+            )
+            .placeholder(tr("none"))
+            .style(style.list.mk_theme());
+        let profile_input = text_input(&tr("new profile name"), &self.profile_input)
+            .on_input(Message::ProfileInput)
+            .on_submit(Message::ProfileSaveAs)
+            .width(200);
+        let profile_save = Button::new(Text::new(tr("Save as Profile"))).on_press(Message::ProfileSaveAs);
+        let mut import_label = Text::new(tr("Import an existing hyprland.conf"));
+        let import_select = Button::new(Text::new(tr("Choose File"))).on_press(Message::ImportPrompt);
 
 
         let mut theme_row = Row::new().spacing(10);
         let mut primary_row = Row::new().spacing(10);
         let mut secondary_row = Row::new().spacing(10);
         let mut wallpaper_row = Row::new().spacing(10);
+        let mut profile_row = Row::new().spacing(10);
+        let mut import_row = Row::new().spacing(10);
 
         if self.index == 0 {
             theme_label = theme_label.style(sel_text.mk_theme())
@@ -79,6 +105,10 @@ impl Configurator {
             secondary_label = secondary_label.style(sel_text.mk_theme());
         } else if self.index == 3 {
             wallpaper_label = wallpaper_label.style(sel_text.mk_theme());
+        } else if self.index == 4 {
+            profile_label = profile_label.style(sel_text.mk_theme());
+        } else if self.index == 5 {
+            import_label = import_label.style(sel_text.mk_theme());
         }
         theme_row = theme_row
             .push(theme_label)
@@ -94,11 +124,22 @@ impl Configurator {
         wallpaper_row = wallpaper_row
             .push(wallpaper_label)
             .push(wallpaper_select);
-        settings.push(theme_row).push(primary_row).push(secondary_row).push(wallpaper_row).spacing(10)
+        profile_row = profile_row
+            .push(profile_label)
+            .push(profile_pick)
+            .push(profile_input)
+            .push(profile_save);
+        import_row = import_row
+            .push(import_label)
+            .push(import_select);
+        let mut settings = settings.push(theme_row).push(primary_row).push(secondary_row).push(wallpaper_row).push(profile_row).push(import_row).spacing(10);
+        for warning in &self.cfg_warnings {
+            settings = settings.push(Text::new(format!("cfg.toml: {warning}")).style(TextStyle {color: style.application.danger}.mk_theme()));
+        }
+        settings
     }
     pub fn bind_page(&self, style: ThemeCustom) -> Column<Message> {
-        let settings = Column::new();
-        //let selection marker: Text = Text::new("=>");
+        let mut settings = Column::new().spacing(10);
         let sel_text = TextStyle {color: style.application.success};
         let primary_pick = pick_list(
             &ShortcutKey::ALL[..], 
@@ -127,141 +168,696 @@ impl Configurator {
         }
         let mut primary_label: Text = Text::new(primary_txt);
         let mut secondary_label: Text = Text::new(secondary_txt);
+        let mut add_label = Text::new(tr("Add a new keybinding"));
+        let add_button = Button::new(Text::new(tr("Add"))).on_press(Message::AddBind);
+
+        let add_index = self.binds.len() + 2;
+        if self.index == 0 {
+            primary_label = primary_label.style(sel_text.mk_theme());
+        } else if self.index == 1 {
+            secondary_label = secondary_label.style(sel_text.mk_theme());
+        } else if self.index == add_index {
+            add_label = add_label.style(sel_text.mk_theme());
+        }
+        let primary_row = Row::new()
+            .push(primary_label)
+            .push(primary_pick)
+            .spacing(10);
+        let secondary_row = Row::new()
+            .push(secondary_label)
+            .push(secondary_pick)
+            .spacing(10);
+        settings = settings.push(primary_row).push(secondary_row);
 
+        let conflicts = self.conflicts();
+        for i in 0..self.binds.len() {
+            let bind = &self.binds[i];
+            let mut bind_row = Row::new().spacing(10);
+            if self.index == i + 2 {
+                bind_row = bind_row.push(Text::new("=>"));
+            }
+            bind_row = bind_row.push(Text::new(format!("{}.", i + 1)));
+            let header_select = pick_list(
+                &BindKey::ALL[..],
+                Some(bind.header),
+                move |x| Message::BindHeaderChanged(i, x),
+                )
+                .placeholder("choose")
+                .style(style.list.mk_theme());
+            bind_row = bind_row.push(header_select);
+            for modifier in ShortcutKey::ALL {
+                let mut mod_select = Button::new(Text::new(rip_shortcut(Some(modifier)))).on_press(Message::BindModToggled(i, modifier));
+                if bind.mods.contains(&modifier) {
+                    mod_select = mod_select.style(style.secondary.mk_theme());
+                }
+                bind_row = bind_row.push(mod_select);
+            }
+            let mut key_select = Button::new(Text::new(bind.key.clone())).on_press(Message::Capture(CaptureInput::Bind(i))).width(50);
+            if self.capture_next == Some(CaptureInput::Bind(i)) {
+                key_select = key_select.style(style.secondary.mk_theme());
+            }
+            let dispatcher_select = pick_list(
+                &Dispatcher::ALL[..],
+                Some(bind.dispatcher),
+                move |x| Message::BindDispatcherChanged(i, x),
+                )
+                .placeholder("choose")
+                .style(style.list.mk_theme());
+            let arg_input = text_input(&tr("argument"), &bind.arg)
+                .on_input(move |x| Message::BindArgChanged(i, x))
+                .width(200);
+            let remove = Button::new(Text::new(tr("Remove"))).on_press(Message::RemoveBind(i));
+            bind_row = bind_row
+                .push(key_select)
+                .push(dispatcher_select)
+                .push(arg_input)
+                .push(remove);
+            if let Some(conflict) = conflicts.iter().find(|x| x.index == Some(i)) { //point out clashing keys and offer a free one
+                let clash_txt = match conflict.other {
+                    Some(other) => format!("{}{}", tr("Same keys as binding "), other + 1),
+                    None => tr("Same keys as a built-in binding"),
+                };
+                bind_row = bind_row.push(Text::new(clash_txt).style(TextStyle {color: style.application.danger}.mk_theme()));
+                if let Some(key) = &conflict.suggestion {
+                    let suggestion = Button::new(Text::new(format!("{}{}", tr("Use "), rip_key(key))))
+                        .on_press(Message::UseSuggestedKey(i, key.clone()));
+                    bind_row = bind_row.push(suggestion);
+                }
+            }
+            settings = settings.push(bind_row);
+        }
+        let add_row = Row::new()
+            .push(add_label)
+            .push(add_button)
+            .spacing(10);
+        settings = settings.push(add_row);
 
-        let mut exit_sc_label = Text::new(tr("Exit the Desktop Session"));
-        let exit_header_select = pick_list(
-        &BindKey::ALL[..], 
-        self.exit_header, 
-        Message::ExitHeaderChanged,
-        )
-        .placeholder("choose")
-        .style(style.list.mk_theme());
-        let exit_key = Text::new(self.exit_key.clone());
-        let mut exit_key_select = Button::new(exit_key).on_press(Message::Capture(CaptureInput::ExitKey)).width(50);
-        let mut launch_sc_label: Text = Text::new(tr("Open the App Launcher"));
-        let launch_header_select = pick_list(
-            &BindKey::ALL[..], 
-            self.launch_header, 
-            Message::LaunchHeaderChanged,
+        let mut keys_label = Text::new(tr("Move focus and apps with the primary key and:"));
+        let keys_pick = pick_list(
+            &DirectionKeys::ALL[..],
+            Some(self.directions.keys),
+            Message::DirectionKeysChanged,
             )
             .placeholder("choose")
             .style(style.list.mk_theme());
-        let launch_key = Text::new(self.launch_key.clone());
-        let mut launch_key_select = Button::new(launch_key).on_press(Message::Capture(CaptureInput::LaunchKey)).width(50);
-        let mut kill_sc_label: Text = Text::new(tr("Close the Currently Focused App"));
-        let kill_header_select = pick_list(
-            &BindKey::ALL[..], 
-            self.kill_header, 
-            Message::KillHeaderChanged,
+        let mut resize_label = Text::new(tr("Resize the focused app with the primary key,"));
+        let resize_keys: Vec<ShortcutKey> = ShortcutKey::ALL.iter().filter(|x| Some(**x) != self.primary_key && Some(**x) != self.secondary_key).copied().collect(); //held with the primary key, either shortcut key would land on focus or move
+        let resize_pick = pick_list(
+            resize_keys,
+            Some(self.directions.resize_mod),
+            Message::ResizeModChanged,
             )
             .placeholder("choose")
             .style(style.list.mk_theme());
-        let kill_key = Text::new(self.kill_key.clone());
-        let mut kill_key_select = Button::new(kill_key).on_press(Message::Capture(CaptureInput::KillKey)).width(50);
-        let mut mini_sc_label: Text = Text::new(tr("Minimize the Focused App"));
-        let mini_header_select = pick_list(
-         &BindKey::ALL[..], 
-         self.minimize_header, 
-         Message::MiniHeaderChanged,
-         )
+        let mut resize_on = Button::new(Text::new(tr("Enable"))).on_press(Message::ResizeToggled(true));
+        let mut resize_off = Button::new(Text::new(tr("Disable"))).on_press(Message::ResizeToggled(false));
+        if self.directions.resize {
+            resize_on = Button::new(Text::new(tr("Enabled"))).on_press(Message::ResizeToggled(true)).style(style.secondary.mk_theme());
+        } else {
+            resize_off = Button::new(Text::new(tr("Disabled"))).on_press(Message::ResizeToggled(false)).style(style.secondary.mk_theme());
+        }
+        let mut submap_label = Text::new(tr("A resize mode, started with the primary key and:"));
+        let mut submap_key = Button::new(Text::new(self.directions.submap_key.clone())).on_press(Message::Capture(CaptureInput::SubmapKey)).width(50);
+        if self.capture_next == Some(CaptureInput::SubmapKey) {
+            submap_key = submap_key.style(style.secondary.mk_theme());
+        }
+        let mut submap_on = Button::new(Text::new(tr("Enable"))).on_press(Message::SubmapToggled(true));
+        let mut submap_off = Button::new(Text::new(tr("Disable"))).on_press(Message::SubmapToggled(false));
+        if self.directions.submap {
+            submap_on = Button::new(Text::new(tr("Enabled"))).on_press(Message::SubmapToggled(true)).style(style.secondary.mk_theme());
+        } else {
+            submap_off = Button::new(Text::new(tr("Disabled"))).on_press(Message::SubmapToggled(false)).style(style.secondary.mk_theme());
+        }
+        if self.index == add_index + 1 {
+            keys_label = keys_label.style(sel_text.mk_theme());
+        } else if self.index == add_index + 2 {
+            resize_label = resize_label.style(sel_text.mk_theme());
+        } else if self.index == add_index + 3 {
+            submap_label = submap_label.style(sel_text.mk_theme());
+        }
+        let keys_row = Row::new()
+            .push(keys_label)
+            .push(keys_pick)
+            .spacing(10);
+        let resize_row = Row::new()
+            .push(resize_label)
+            .push(resize_pick)
+            .push(Text::new(tr("and a direction")))
+            .push(resize_off)
+            .push(resize_on)
+            .spacing(10);
+        let submap_row = Row::new()
+            .push(submap_label)
+            .push(submap_key)
+            .push(submap_off)
+            .push(submap_on)
+            .spacing(10);
+        settings = settings
+            .push(keys_row)
+            .push(resize_row)
+            .push(submap_row);
+        for conflict in conflicts.iter().filter(|x| x.index.is_none()) { //two of our own bindings, e.g. after the secondary key changed to the resize key
+            settings = settings.push(Text::new(conflict.built_in_text()).style(TextStyle {color: style.application.danger}.mk_theme()));
+        }
+        settings
+    }
+    pub fn workspace_page(&self, style: ThemeCustom) -> Column<Message> {
+        let mut settings = Column::new().spacing(10);
+        let sel_text = TextStyle {color: style.application.success};
+        let count_incr = Button::new("+").on_press(Message::Incr(IncrVal::WorkspaceCount)).width(30);
+        let count_decr = Button::new("-").on_press(Message::Decr(IncrVal::WorkspaceCount)).width(30);
+        let count_value_peek = Text::new(format!("{}", self.workspaces.count()));
+        let mut count_label = Text::new(tr("The Number of Workspaces:"));
+
+        let keys_pick = pick_list(
+            &WorkspaceKeys::ALL[..],
+            Some(self.workspaces.keys),
+            Message::WorkspaceKeysChanged,
+            )
             .placeholder("choose")
             .style(style.list.mk_theme());
-        let mini_key = Text::new(self.minimize_key.clone());
-        let mut mini_key_select = Button::new(mini_key).on_press(Message::Capture(CaptureInput::MiniKey)).width(50);
-        let mut scratch_sc_label: Text = Text::new(tr("Retrieve App from Minimization"));
-        let scratch_header_select = pick_list(
-            &BindKey::ALL[..], 
-            self.scratch_header, 
-            Message::ScratchHeaderChanged,
+        let mut keys_label = Text::new(tr("The Keys That Switch Workspaces:"));
+
+        let mut follow_label = Text::new(tr("Follow a window moved to another workspace"));
+        let mut follow_on = Button::new(Text::new(tr("Follow"))).on_press(Message::WorkspaceFollowToggled(true));
+        let mut follow_off = Button::new(Text::new(tr("Stay"))).on_press(Message::WorkspaceFollowToggled(false));
+        if self.workspaces.follow {
+            follow_on = follow_on.style(style.secondary.mk_theme());
+        } else {
+            follow_off = follow_off.style(style.secondary.mk_theme());
+        }
+
+        let mut cycle_label = Text::new(tr("Next and previous workspace on period, comma and the mouse wheel"));
+        let mut cycle_on = Button::new(Text::new(tr("Enable"))).on_press(Message::WorkspaceCycleToggled(true));
+        let mut cycle_off = Button::new(Text::new(tr("Disable"))).on_press(Message::WorkspaceCycleToggled(false));
+        if self.workspaces.cycle {
+            cycle_on = Button::new(Text::new(tr("Enabled"))).on_press(Message::WorkspaceCycleToggled(true)).style(style.secondary.mk_theme());
+        } else {
+            cycle_off = Button::new(Text::new(tr("Disabled"))).on_press(Message::WorkspaceCycleToggled(false)).style(style.secondary.mk_theme());
+        }
+
+        if self.index == 0 {
+            count_label = count_label.style(sel_text.mk_theme());
+        } else if self.index == 1 {
+            keys_label = keys_label.style(sel_text.mk_theme());
+        } else if self.index == 2 {
+            follow_label = follow_label.style(sel_text.mk_theme());
+        } else if self.index == 3 {
+            cycle_label = cycle_label.style(sel_text.mk_theme());
+        }
+
+        let count_row = Row::new()
+            .push(count_label)
+            .push(count_decr)
+            .push(count_value_peek)
+            .push(count_incr)
+            .spacing(10);
+        let keys_row = Row::new()
+            .push(keys_label)
+            .push(keys_pick)
+            .spacing(10);
+        let follow_row = Row::new()
+            .push(follow_label)
+            .push(follow_off)
+            .push(follow_on)
+            .spacing(10);
+        let cycle_row = Row::new()
+            .push(cycle_label)
+            .push(cycle_off)
+            .push(cycle_on)
+            .spacing(10);
+        settings = settings
+            .push(count_row)
+            .push(keys_row)
+            .push(follow_row)
+            .push(cycle_row)
+            .push(Text::new(tr("Keep a workspace on one monitor, leave empty to let it open anywhere:")));
+        for ws in 1..=self.workspaces.count() as u8 {
+            let monitor_input = text_input(&tr("monitor name, e.g. DP-1"), &self.workspaces.monitor(ws))
+                .on_input(move |x| Message::WorkspaceMonitorInput(ws, x))
+                .width(300);
+            let monitor_row = Row::new()
+                .push(Text::new(format!("{} {ws}", tr("Workspace"))))
+                .push(monitor_input)
+                .spacing(10);
+            settings = settings.push(monitor_row);
+        }
+        settings
+    }
+    pub fn layout_page(&self, style: ThemeCustom) -> Column<Message> {
+        let mut settings = Column::new().spacing(10);
+        let sel_text = TextStyle {color: style.application.success};
+        let head_text = TextStyle {color: style.application.primary};
+        let pri = match self.primary_key {Some(x) => x.to_string(), None => String::new()};
+
+        let engine_pick = pick_list(
+            &LayoutEngine::ALL[..],
+            Some(self.layout.engine),
+            Message::LayoutEngineChanged,
             )
             .placeholder("choose")
             .style(style.list.mk_theme());
-        let scratch_key = Text::new(self.scratch_key.clone());
-        let mut scratch_key_select = Button::new(scratch_key).on_press(Message::Capture(CaptureInput::ScratchKey)).width(50);
-        
-        match self.capture_next.as_ref().unwrap() {
-            CaptureInput::NoKey => {
-            }
-            CaptureInput::ExitKey => {
-                exit_key_select = exit_key_select.style(style.secondary.mk_theme());
-            }
-            CaptureInput::KillKey => {
-                kill_key_select = kill_key_select.style(style.secondary.mk_theme());
+        let mut engine_label = Text::new(tr("How Windows Are Tiled:"));
+
+        let mut pseudo_label = Text::new(format!("{} {pri}+{PSEUDO_KEY}", tr("Let windows keep their own size inside their tile with")));
+        let mut pseudo_on = Button::new(Text::new(tr("Enable"))).on_press(Message::PseudotileToggled(true));
+        let mut pseudo_off = Button::new(Text::new(tr("Disable"))).on_press(Message::PseudotileToggled(false));
+        if self.layout.pseudotile {
+            pseudo_on = Button::new(Text::new(tr("Enabled"))).on_press(Message::PseudotileToggled(true)).style(style.secondary.mk_theme());
+        } else {
+            pseudo_off = Button::new(Text::new(tr("Disabled"))).on_press(Message::PseudotileToggled(false)).style(style.secondary.mk_theme());
+        }
+        let mut split_label = Text::new(tr("Keep the direction of a split when windows move"));
+        let mut split_on = Button::new(Text::new(tr("Keep"))).on_press(Message::PreserveSplitToggled(true));
+        let mut split_off = Button::new(Text::new(tr("Recalculate"))).on_press(Message::PreserveSplitToggled(false));
+        if self.layout.preserve_split {
+            split_on = split_on.style(style.secondary.mk_theme());
+        } else {
+            split_off = split_off.style(style.secondary.mk_theme());
+        }
+
+        let orientation_pick = pick_list(
+            &MasterOrientation::ALL[..],
+            Some(self.layout.orientation),
+            Message::OrientationChanged,
+            )
+            .placeholder("choose")
+            .style(style.list.mk_theme());
+        let mut orientation_label = Text::new(tr("Where the Master Window Goes:"));
+        let mut new_label = Text::new(tr("New windows become the master window"));
+        let mut new_on = Button::new(Text::new(tr("Master"))).on_press(Message::NewIsMasterToggled(true));
+        let mut new_off = Button::new(Text::new(tr("Stack"))).on_press(Message::NewIsMasterToggled(false));
+        if self.layout.new_is_master {
+            new_on = new_on.style(style.secondary.mk_theme());
+        } else {
+            new_off = new_off.style(style.secondary.mk_theme());
+        }
+
+        if self.index == 0 {
+            engine_label = engine_label.style(sel_text.mk_theme());
+        } else if self.index == 1 {
+            pseudo_label = pseudo_label.style(sel_text.mk_theme());
+        } else if self.index == 2 {
+            split_label = split_label.style(sel_text.mk_theme());
+        } else if self.index == 3 {
+            orientation_label = orientation_label.style(sel_text.mk_theme());
+        } else if self.index == 4 {
+            new_label = new_label.style(sel_text.mk_theme());
+        }
+
+        let engine_row = Row::new()
+            .push(engine_label)
+            .push(engine_pick)
+            .spacing(10);
+        let pseudo_row = Row::new()
+            .push(pseudo_label)
+            .push(pseudo_off)
+            .push(pseudo_on)
+            .spacing(10);
+        let split_row = Row::new()
+            .push(split_label)
+            .push(split_off)
+            .push(split_on)
+            .spacing(10);
+        let orientation_row = Row::new()
+            .push(orientation_label)
+            .push(orientation_pick)
+            .spacing(10);
+        let new_row = Row::new()
+            .push(new_label)
+            .push(new_off)
+            .push(new_on)
+            .spacing(10);
+        let (dwindle_note, master_note) = match self.layout.engine { //both are kept, only the one in use is written
+            LayoutEngine::Dwindle => (
+                format!("{} {pri}+{SPLIT_KEY}.", tr("Dwindle, in use. Flip the split of the focused window with")),
+                tr("Master, not in use:"),
+            ),
+            LayoutEngine::Master => (
+                tr("Dwindle, not in use:"),
+                format!("{} {pri}+{SWAP_MASTER_KEY}.", tr("Master, in use. Swap the focused window with the master window with")),
+            ),
+        };
+        settings = settings
+            .push(engine_row)
+            .push(Text::new(dwindle_note).style(head_text.mk_theme()))
+            .push(pseudo_row)
+            .push(split_row)
+            .push(Text::new(master_note).style(head_text.mk_theme()))
+            .push(orientation_row)
+            .push(new_row);
+        settings
+    }
+    pub fn rules_page(&self, style: ThemeCustom) -> Column<Message> {
+        let mut settings = Column::new().spacing(10);
+        let sel_text = TextStyle {color: style.application.success};
+        let mut clients_label = Text::new(tr("Start a rule from an open window:"));
+        let clients_button = Button::new(Text::new(tr("List Open Windows"))).on_press(Message::RulesListClients);
+        let mut add_label = Text::new(tr("Start an empty rule:"));
+        let add_button = Button::new(Text::new(tr("Add a Rule"))).on_press(Message::RuleAdd);
+        if self.index == 0 {
+            clients_label = clients_label.style(sel_text.mk_theme());
+        } else if self.index == 1 {
+            add_label = add_label.style(sel_text.mk_theme());
+        }
+        let clients_row = Row::new()
+            .push(clients_label)
+            .push(clients_button)
+            .spacing(10);
+        let add_row = Row::new()
+            .push(add_label)
+            .push(add_button)
+            .spacing(10);
+        settings = settings.push(clients_row);
+        for i in 0..self.clients.len() {
+            let client = &self.clients[i];
+            let client_row = Row::new()
+                .push(Button::new(Text::new(tr("Use"))).on_press(Message::RuleFromClient(i)))
+                .push(Text::new(format!("{} ({})", client.class, client.title)))
+                .spacing(10);
+            settings = settings.push(client_row);
+        }
+        settings = settings.push(add_row);
+        if self.window_rules.len() == 0 {
+            settings = settings.push(Text::new(tr("No window rules yet.")));
+        }
+        for i in 0..self.window_rules.len() {
+            let rule = &self.window_rules[i];
+            let action_pick = pick_list(
+                &RuleAction::ALL[..],
+                Some(rule.action),
+                move |x| Message::RuleActionChanged(i, x),
+                )
+                .placeholder("choose")
+                .style(style.list.mk_theme());
+            let remove = Button::new(Text::new(tr("Remove"))).on_press(Message::RuleRemove(i));
+            let mut action_row = Row::new()
+                .push(Text::new(format!("{} {}:", tr("Rule"), i + 1)).style(TextStyle {color: style.application.primary}.mk_theme()))
+                .push(action_pick)
+                .spacing(10);
+            if rule.action.takes_arg() {
+                let arg_input = text_input(&rule.action.arg_hint(), &rule.arg)
+                    .on_input(move |x| Message::RuleArgInput(i, x))
+                    .width(200);
+                action_row = action_row.push(arg_input);
             }
-            CaptureInput::LaunchKey => {
-                launch_key_select = launch_key_select.style(style.secondary.mk_theme());
+            action_row = action_row.push(remove);
+            let class_input = text_input(&tr("class, e.g. ^(firefox)$"), &rule.class)
+                .on_input(move |x| Message::RuleClassInput(i, x))
+                .width(250);
+            let title_input = text_input(&tr("title, empty for any"), &rule.title)
+                .on_input(move |x| Message::RuleTitleInput(i, x))
+                .width(250);
+            let match_row = Row::new()
+                .push(Text::new(tr("For windows matching:")))
+                .push(class_input)
+                .push(title_input)
+                .spacing(10);
+            let floating_pick = pick_list(
+                &RuleState::ALL[..],
+                Some(rule.floating),
+                move |x| Message::RuleFloatingChanged(i, x),
+                )
+                .style(style.list.mk_theme());
+            let xwayland_pick = pick_list(
+                &RuleState::ALL[..],
+                Some(rule.xwayland),
+                move |x| Message::RuleXwaylandChanged(i, x),
+                )
+                .style(style.list.mk_theme());
+            let state_row = Row::new()
+                .push(Text::new(tr("Floating:")))
+                .push(floating_pick)
+                .push(Text::new(tr("XWayland:")))
+                .push(xwayland_pick)
+                .spacing(10);
+            settings = settings
+                .push(action_row)
+                .push(match_row)
+                .push(state_row);
+            if rip_window_rule(rule).is_none() { //saved, but left out of hyprland.conf
+                let unfinished = if rule.action.takes_arg() && rule.arg.trim().len() == 0 {
+                    tr("This rule is left out until its action has a value.")
+                } else {
+                    tr("This rule is left out until it matches something.")
+                };
+                settings = settings.push(Text::new(unfinished).style(TextStyle {color: style.application.danger}.mk_theme()));
             }
-            CaptureInput::MiniKey => {
-                mini_key_select = mini_key_select.style(style.secondary.mk_theme());
+        }
+        settings
+    }
+    pub fn monitor_page(&self, style: ThemeCustom) -> Column<Message> {
+        let mut settings = Column::new().spacing(10);
+        let sel_text = TextStyle {color: style.application.success};
+        let mut detect_label = Text::new(tr("Read the connected monitors from Hyprland:"));
+        let detect_button = Button::new(Text::new(tr("Detect"))).on_press(Message::MonitorsDetect);
+        let mut arrange_label = Text::new(tr("Place the monitors side by side, in this order:"));
+        let arrange_button = Button::new(Text::new(tr("Arrange"))).on_press(Message::MonitorsArrange);
+        if self.index == 0 {
+            detect_label = detect_label.style(sel_text.mk_theme());
+        } else if self.index == 1 {
+            arrange_label = arrange_label.style(sel_text.mk_theme());
+        }
+        let detect_row = Row::new()
+            .push(detect_label)
+            .push(detect_button)
+            .spacing(10);
+        let arrange_row = Row::new()
+            .push(arrange_label)
+            .push(arrange_button)
+            .spacing(10);
+        settings = settings.push(detect_row).push(arrange_row);
+        if self.monitors.len() == 0 {
+            settings = settings.push(Text::new(tr("No monitors are set up, Hyprland places them automatically.")));
+        }
+        for i in 0..self.monitors.len() {
+            let monitor = &self.monitors[i];
+            let output = self.outputs.iter().find(|x| x.name == monitor.name);
+            let name = match output {
+                Some(output) if output.description.len() > 0 => format!("{} ({})", monitor.name, output.description),
+                Some(..) => monitor.name.clone(),
+                None => format!("{} {}", monitor.name, tr("(not connected)")),
+            };
+            let remove = Button::new(Text::new(tr("Remove"))).on_press(Message::MonitorRemove(i));
+            let name_row = Row::new()
+                .push(Text::new(name).style(TextStyle {color: style.application.primary}.mk_theme()))
+                .push(remove)
+                .spacing(10);
+            let mut mode_row = Row::new().spacing(10).push(Text::new(tr("Resolution and refresh rate:")));
+            match output {
+                Some(output) if output.modes.len() > 0 => {
+                    let selected = output.modes.iter().find(|x| mode_matches(monitor, x)).cloned();
+                    let mode_pick = pick_list(
+                        output.modes.clone(),
+                        selected,
+                        move |x| Message::MonitorModeChanged(i, x),
+                        )
+                        .placeholder(monitor.mode())
+                        .style(style.list.mk_theme());
+                    mode_row = mode_row.push(mode_pick);
+                }
+                _ => {
+                    mode_row = mode_row.push(Text::new(monitor.mode()));
+                }
             }
-            CaptureInput::ScratchKey => {
-                scratch_key_select = scratch_key_select.style(style.secondary.mk_theme());
+            let x_input = text_input("0", &monitor.x.to_string())
+                .on_input(move |x| Message::MonitorXInput(i, x))
+                .width(80);
+            let y_input = text_input("0", &monitor.y.to_string())
+                .on_input(move |x| Message::MonitorYInput(i, x))
+                .width(80);
+            let position_row = Row::new()
+                .push(Text::new(tr("Position:")))
+                .push(x_input)
+                .push(Text::new("x"))
+                .push(y_input)
+                .spacing(10);
+            let mut scale_decr = Button::new("-").on_press(Message::Decr(IncrVal::MonitorScale(i))).width(30);
+            if monitor.scale <= 0.25 {
+                scale_decr = scale_decr.style(style.secondary.mk_theme());
             }
+            let scale_incr = Button::new("+").on_press(Message::Incr(IncrVal::MonitorScale(i))).width(30);
+            let transform_pick = pick_list(
+                &Transform::ALL[..],
+                Some(monitor.transform),
+                move |x| Message::MonitorTransformChanged(i, x),
+                )
+                .placeholder("choose")
+                .style(style.list.mk_theme());
+            let scale_row = Row::new()
+                .push(Text::new(tr("Scale:")))
+                .push(scale_decr)
+                .push(Text::new(format!("{}", monitor.scale)))
+                .push(scale_incr)
+                .push(Text::new(tr("Rotation:")))
+                .push(transform_pick)
+                .spacing(10);
+            settings = settings
+                .push(name_row)
+                .push(mode_row)
+                .push(position_row)
+                .push(scale_row);
         }
-        let mut primary_row = Row::new();
-        let mut secondary_row = Row::new();
-        let mut exit_sc_row = Row::new();
-        let mut launch_sc_row = Row::new();
-        let mut kill_sc_row = Row::new();
-        let mut mini_sc_row = Row::new();
-        let mut scratch_sc_row = Row::new();
+        settings
+    }
+    pub fn input_page(&self, style: ThemeCustom) -> Column<Message> {
+        let mut settings = Column::new().spacing(10);
+        let sel_text = TextStyle {color: style.application.success};
+
+        let mut layout_label = Text::new(tr("Keyboard Layout:"));
+        let mut variant_label = Text::new(tr("Layout Variant:"));
         if self.index == 0 {
-            primary_label = primary_label.style(sel_text.mk_theme());
+            layout_label = layout_label.style(sel_text.mk_theme());
         } else if self.index == 1 {
-            secondary_label = secondary_label.style(sel_text.mk_theme());
-        } else if self.index == 2 {
-            exit_sc_label = exit_sc_label.style(sel_text.mk_theme());
+            variant_label = variant_label.style(sel_text.mk_theme());
+        }
+        let layout_row = if self.xkb.layouts.len() > 0 {
+            let layout_pick = pick_list(
+                self.xkb.layouts.clone(),
+                self.xkb.layouts.iter().find(|x| x.code == self.input.kb_layout).cloned(),
+                |x| Message::KbLayoutChanged(x.code),
+                )
+                .placeholder(self.input.kb_layout.clone()) //a layout xkb doesn't list, or several separated by commas
+                .style(style.list.mk_theme());
+            Row::new().push(layout_label).push(layout_pick).spacing(10)
+        } else {
+            let layout_input = text_input(&tr("layout code, e.g. us"), &self.input.kb_layout)
+                .on_input(Message::KbLayoutChanged)
+                .width(200);
+            Row::new().push(layout_label).push(layout_input).spacing(10)
+        };
+        let variants = self.xkb.variants_of(&self.input.kb_layout);
+        let variant_row = if self.xkb.layouts.len() > 0 {
+            let mut variant_pick = pick_list(
+                variants.clone(),
+                variants.iter().find(|x| x.code == self.input.kb_variant).cloned(),
+                |x| Message::KbVariantChanged(x.code),
+                )
+                .placeholder(tr("default"))
+                .style(style.list.mk_theme());
+            if self.input.kb_variant.len() > 0 && variants.iter().all(|x| x.code != self.input.kb_variant) {
+                variant_pick = variant_pick.placeholder(self.input.kb_variant.clone());
+            }
+            let variant_default = Button::new(Text::new(tr("Default"))).on_press(Message::KbVariantChanged(String::new()));
+            Row::new().push(variant_label).push(variant_pick).push(variant_default).spacing(10)
+        } else {
+            let variant_input = text_input(&tr("variant code, empty for the default"), &self.input.kb_variant)
+                .on_input(Message::KbVariantChanged)
+                .width(200);
+            Row::new().push(variant_label).push(variant_input).spacing(10)
+        };
+
+        let rate_incr = Button::new("+").on_press(Message::Incr(IncrVal::RepeatRate)).width(30);
+        let rate_decr = Button::new("-").on_press(Message::Decr(IncrVal::RepeatRate)).width(30);
+        let rate_value_peek = Text::new(format!("{}", self.input.repeat_rate));
+        let mut rate_label = Text::new(tr("Key Repeats per Second:"));
+        let delay_incr = Button::new("+").on_press(Message::Incr(IncrVal::RepeatDelay)).width(30);
+        let delay_decr = Button::new("-").on_press(Message::Decr(IncrVal::RepeatDelay)).width(30);
+        let delay_value_peek = Text::new(format!("{} ms", self.input.repeat_delay));
+        let mut delay_label = Text::new(tr("Delay Before a Held Key Repeats:"));
+        let sens_incr = Button::new("+").on_press(Message::Incr(IncrVal::Sensitivity)).width(30);
+        let sens_decr = Button::new("-").on_press(Message::Decr(IncrVal::Sensitivity)).width(30);
+        let sens_value_peek = Text::new(format!("{:.1}", self.input.sensitivity));
+        let mut sens_label = Text::new(tr("Pointer Sensitivity:"));
+
+        let mut scroll_label = Text::new(tr("Natural scrolling with a mouse"));
+        let mut scroll_on = Button::new(Text::new(tr("Enable"))).on_press(Message::NaturalScrollToggled(true));
+        let mut scroll_off = Button::new(Text::new(tr("Disable"))).on_press(Message::NaturalScrollToggled(false));
+        if self.input.natural_scroll {
+            scroll_on = Button::new(Text::new(tr("Enabled"))).on_press(Message::NaturalScrollToggled(true)).style(style.secondary.mk_theme());
+        } else {
+            scroll_off = Button::new(Text::new(tr("Disabled"))).on_press(Message::NaturalScrollToggled(false)).style(style.secondary.mk_theme());
+        }
+        let mut touch_scroll_label = Text::new(tr("Natural scrolling with a touchpad"));
+        let mut touch_scroll_on = Button::new(Text::new(tr("Enable"))).on_press(Message::TouchpadScrollToggled(true));
+        let mut touch_scroll_off = Button::new(Text::new(tr("Disable"))).on_press(Message::TouchpadScrollToggled(false));
+        if self.input.touchpad_natural_scroll {
+            touch_scroll_on = Button::new(Text::new(tr("Enabled"))).on_press(Message::TouchpadScrollToggled(true)).style(style.secondary.mk_theme());
+        } else {
+            touch_scroll_off = Button::new(Text::new(tr("Disabled"))).on_press(Message::TouchpadScrollToggled(false)).style(style.secondary.mk_theme());
+        }
+        let mut tap_label = Text::new(tr("Tap the touchpad to click"));
+        let mut tap_on = Button::new(Text::new(tr("Enable"))).on_press(Message::TapToClickToggled(true));
+        let mut tap_off = Button::new(Text::new(tr("Disable"))).on_press(Message::TapToClickToggled(false));
+        if self.input.tap_to_click {
+            tap_on = Button::new(Text::new(tr("Enabled"))).on_press(Message::TapToClickToggled(true)).style(style.secondary.mk_theme());
+        } else {
+            tap_off = Button::new(Text::new(tr("Disabled"))).on_press(Message::TapToClickToggled(false)).style(style.secondary.mk_theme());
+        }
+
+        let follow_pick = pick_list(
+            &FollowMouse::ALL[..],
+            Some(self.input.follow_mouse),
+            Message::FollowMouseChanged,
+            )
+            .placeholder("choose")
+            .style(style.list.mk_theme());
+        let mut follow_label = Text::new(tr("Keyboard Focus:"));
+
+        if self.index == 2 {
+            rate_label = rate_label.style(sel_text.mk_theme());
         } else if self.index == 3 {
-            launch_sc_label = launch_sc_label.style(sel_text.mk_theme());
+            delay_label = delay_label.style(sel_text.mk_theme());
         } else if self.index == 4 {
-            kill_sc_label = kill_sc_label.style(sel_text.mk_theme());
+            sens_label = sens_label.style(sel_text.mk_theme());
         } else if self.index == 5 {
-            mini_sc_label = mini_sc_label.style(sel_text.mk_theme());
+            scroll_label = scroll_label.style(sel_text.mk_theme());
         } else if self.index == 6 {
-            scratch_sc_label = scratch_sc_label.style(sel_text.mk_theme());
+            touch_scroll_label = touch_scroll_label.style(sel_text.mk_theme());
+        } else if self.index == 7 {
+            tap_label = tap_label.style(sel_text.mk_theme());
+        } else if self.index == 8 {
+            follow_label = follow_label.style(sel_text.mk_theme());
         }
-        primary_row = primary_row
-            .push(primary_label)
-            .push(primary_pick)
+
+        let rate_row = Row::new()
+            .push(rate_label)
+            .push(rate_decr)
+            .push(rate_value_peek)
+            .push(rate_incr)
             .spacing(10);
-        secondary_row = secondary_row
-            .push(secondary_label)
-            .push(secondary_pick)
+        let delay_row = Row::new()
+            .push(delay_label)
+            .push(delay_decr)
+            .push(delay_value_peek)
+            .push(delay_incr)
             .spacing(10);
-        exit_sc_row = exit_sc_row
-            .push(exit_sc_label)
-            .push(exit_header_select)
-            .push(exit_key_select)
+        let sens_row = Row::new()
+            .push(sens_label)
+            .push(sens_decr)
+            .push(sens_value_peek)
+            .push(sens_incr)
             .spacing(10);
-        launch_sc_row = launch_sc_row
-            .push(launch_sc_label)
-            .push(launch_header_select)
-            .push(launch_key_select)
+        let scroll_row = Row::new()
+            .push(scroll_label)
+            .push(scroll_off)
+            .push(scroll_on)
             .spacing(10);
-        kill_sc_row = kill_sc_row
-            .push(kill_sc_label)
-            .push(kill_header_select)
-            .push(kill_key_select)
+        let touch_scroll_row = Row::new()
+            .push(touch_scroll_label)
+            .push(touch_scroll_off)
+            .push(touch_scroll_on)
             .spacing(10);
-        mini_sc_row = mini_sc_row
-            .push(mini_sc_label)
-            .push(mini_header_select)
-            .push(mini_key_select)
+        let tap_row = Row::new()
+            .push(tap_label)
+            .push(tap_off)
+            .push(tap_on)
             .spacing(10);
-        scratch_sc_row = scratch_sc_row
-            .push(scratch_sc_label)
-            .push(scratch_header_select)
-            .push(scratch_key_select)
+        let follow_row = Row::new()
+            .push(follow_label)
+            .push(follow_pick)
             .spacing(10);
+        settings = settings
+            .push(layout_row)
+            .push(variant_row)
+            .push(rate_row)
+            .push(delay_row)
+            .push(sens_row)
+            .push(scroll_row)
+            .push(touch_scroll_row)
+            .push(tap_row)
+            .push(follow_row);
         settings
-            .push(primary_row)
-            .push(secondary_row)
-            .push(exit_sc_row)
-            .push(launch_sc_row)
-            .push(kill_sc_row)
-            .push(mini_sc_row)
-            .push(scratch_sc_row).spacing(10)
     }
     pub fn bar_page(&self, style: ThemeCustom) -> Column<Message> {
         let settings = Column::new();
@@ -379,7 +975,7 @@ impl Configurator {
             .push(right_row).spacing(10)
     }
     pub fn anim_page(&self, style: ThemeCustom) -> Column<Message> {
-        let settings = Column::new();
+        let mut settings = Column::new();
         //let selection_marker: Text = Text::new("=>");
         let sel_text = TextStyle {color: style.application.success};
         let width_incr = Button::new("+").on_press(Message::Incr(IncrVal::WidthVal)).width(30);
@@ -488,12 +1084,230 @@ impl Configurator {
             .push(blur_label)
             .push(blur_off)
             .push(blur_on);
-        settings
+        settings = settings
             .push(width_row)
             .push(gaps_row)
             .push(rad_row)
             .push(win_row)
             .push(work_row)
-            .push(blur_row).spacing(10)
+            .push(blur_row).spacing(10);
+
+        let mut popin_label = Text::new(tr("How small windows start out when popping in:"));
+        if self.index == 6 {
+            popin_label = popin_label.style(sel_text.mk_theme());
+        }
+        let popin_row = Row::new()
+            .push(popin_label)
+            .push(Button::new("-").on_press(Message::Decr(IncrVal::Popin)).width(30))
+            .push(Text::new(format!("{}%", self.animations.popin)))
+            .push(Button::new("+").on_press(Message::Incr(IncrVal::Popin)).width(30))
+            .spacing(10);
+        settings = settings
+            .push(popin_row)
+            .push(Text::new(tr("Speed and curve of each animation, lower is faster:")).style(TextStyle {color: style.application.primary}.mk_theme()));
+        let curves = self.animations.curves();
+        for i in 0..AnimationName::ALL.len() {
+            let name = AnimationName::ALL[i];
+            let entry = self.animations.entry(name);
+            let mut name_label = Text::new(format!("{name}:"));
+            if self.index == i + 7 {
+                name_label = name_label.style(sel_text.mk_theme());
+            }
+            let curve_pick = pick_list(
+                curves.clone(),
+                Some(entry.curve.clone()),
+                move |x| Message::AnimCurveChanged(name, x),
+                )
+                .style(style.list.mk_theme());
+            let mut anim_row = Row::new()
+                .push(name_label)
+                .push(Button::new("-").on_press(Message::Decr(IncrVal::AnimSpeed(name))).width(30))
+                .push(Text::new(format!("{}", entry.speed)))
+                .push(Button::new("+").on_press(Message::Incr(IncrVal::AnimSpeed(name))).width(30))
+                .push(curve_pick)
+                .spacing(10);
+            if name.switchable() && !name.preset_off(self.window_anim.unwrap(), self.work_anim.unwrap()) {
+                let mut anim_off = Button::new(Text::new(tr("Disable"))).on_press(Message::AnimToggled(name, false));
+                let mut anim_on = Button::new(Text::new(tr("Enable"))).on_press(Message::AnimToggled(name, true));
+                if entry.enabled {
+                    anim_on = Button::new(Text::new(tr("Enabled"))).on_press(Message::AnimToggled(name, true)).style(style.secondary.mk_theme());
+                } else {
+                    anim_off = Button::new(Text::new(tr("Disabled"))).on_press(Message::AnimToggled(name, false)).style(style.secondary.mk_theme());
+                }
+                anim_row = anim_row.push(anim_off).push(anim_on);
+            } else {
+                anim_row = anim_row.push(Text::new(tr("switched off with the preset above")));
+            }
+            settings = settings.push(anim_row);
+        }
+
+        let add_row = Row::new()
+            .push(Text::new(tr("Curves of your own, as x0, y0, x1, y1:")).style(TextStyle {color: style.application.primary}.mk_theme()))
+            .push(Button::new(Text::new(tr("Add a Curve"))).on_press(Message::BezierAdd))
+            .spacing(10);
+        settings = settings.push(add_row);
+        for i in 0..self.animations.beziers.len() {
+            let bezier = &self.animations.beziers[i];
+            let name_input = text_input(&tr("name"), &self.bezier_names[i])
+                .on_input(move |x| Message::BezierNameInput(i, x))
+                .width(150);
+            let points_input = text_input("0.05, 0.9, 0.1, 1.05", &self.bezier_inputs[i])
+                .on_input(move |x| Message::BezierPointsInput(i, x))
+                .width(250);
+            let bezier_row = Row::new()
+                .push(name_input)
+                .push(points_input)
+                .push(Button::new(Text::new(tr("Remove"))).on_press(Message::BezierRemove(i)))
+                .spacing(10);
+            settings = settings.push(bezier_row);
+            let typed = self.bezier_names[i].trim();
+            if typed == "default" {
+                settings = settings.push(Text::new(tr("default is Hyprland's own curve, pick another name.")).style(TextStyle {color: style.application.danger}.mk_theme()));
+            } else if typed != bezier.name.trim() || (typed.len() > 0 && !self.animations.bezier_usable(i)) { //a cfg.toml edited by hand can hold a repeat too
+                settings = settings.push(Text::new(tr("Another curve already has this name, the last free one is kept.")).style(TextStyle {color: style.application.danger}.mk_theme()));
+            } else if bezier.name.trim().len() == 0 { //saved, but left out of hyprland.conf
+                settings = settings.push(Text::new(tr("This curve is left out until it has a name.")).style(TextStyle {color: style.application.danger}.mk_theme()));
+            } else if parse_bezier_points(&self.bezier_inputs[i]).is_none() {
+                settings = settings.push(Text::new(tr("Four numbers separated by commas are needed, the last ones that were are kept.")).style(TextStyle {color: style.application.danger}.mk_theme()));
+            }
+        }
+        settings
+    }
+    pub fn init_page(&self, style: ThemeCustom) -> Column<Message> {
+        let mut settings = Column::new().spacing(10);
+        let sel_text = TextStyle {color: style.application.success};
+        let mut add_label = Text::new(tr("Run a program when you log in:"));
+        let add_input = text_input(&tr("command to run"), &self.autostart_input)
+            .on_input(Message::AutostartInput)
+            .on_submit(Message::AutostartAdd)
+            .width(300);
+        let add_button = Button::new(Text::new(tr("Add"))).on_press(Message::AutostartAdd);
+        if self.index == 0 {
+            add_label = add_label.style(sel_text.mk_theme());
+        }
+        let add_row = Row::new()
+            .push(add_label)
+            .push(add_input)
+            .push(add_button)
+            .spacing(10);
+        settings = settings.push(add_row);
+        if self.autostart.len() == 0 {
+            settings = settings.push(Text::new(tr("Nothing is started at login yet.")));
+        }
+        for i in 0..self.autostart.len() {
+            let entry = &self.autostart[i];
+            let mut entry_label = Text::new(entry.command.clone());
+            if self.index == i + 1 {
+                entry_label = entry_label.style(TextStyle {color: style.application.success}.mk_theme());
+            }
+            let toggle = if entry.enabled {
+                Button::new(Text::new(tr("Enabled"))).on_press(Message::AutostartToggle(i)).style(style.secondary.mk_theme())
+            } else {
+                Button::new(Text::new(tr("Disabled"))).on_press(Message::AutostartToggle(i))
+            };
+            let mut up = Button::new(Text::new(tr("Move Up"))).on_press(Message::AutostartUp(i));
+            let mut down = Button::new(Text::new(tr("Move Down"))).on_press(Message::AutostartDown(i));
+            let remove = Button::new(Text::new(tr("Remove"))).on_press(Message::AutostartRemove(i));
+            if i == 0 {
+                up = up.style(style.secondary.mk_theme());
+            }
+            if i + 1 == self.autostart.len() {
+                down = down.style(style.secondary.mk_theme());
+            }
+            let entry_row = Row::new()
+                .push(entry_label)
+                .push(toggle)
+                .push(up)
+                .push(down)
+                .push(remove)
+                .spacing(10);
+            settings = settings.push(entry_row);
+        }
+        settings
+    }
+    pub fn restore_page(&self, style: ThemeCustom) -> Column<Message> {
+        let mut settings = Column::new().spacing(10);
+        let sel_text = TextStyle {color: style.application.success};
+        settings = settings.push(Text::new(tr("Restore previous configuration")));
+        if self.backups.len() == 0 {
+            settings = settings.push(Text::new(tr("Nothing has been backed up yet, a backup is made every time a save overwrites a file.")));
+        }
+        for i in 0..self.backups.len() {
+            let backup = &self.backups[i];
+            let mut label = Text::new(format!("{}  ({} {})", format_stamp(backup.stamp), backup.files.len(), tr("files")));
+            if self.index == i {
+                label = label.style(sel_text.mk_theme());
+            }
+            let mut show = Button::new(Text::new(tr("Show Changes"))).on_press(Message::BackupSelected(i));
+            let mut backup_row = Row::new().push(label).spacing(10);
+            if self.backup_selected == Some(i) {
+                show = show.style(style.secondary.mk_theme());
+                backup_row = backup_row.push(show).push(Button::new(Text::new(tr("Restore"))).on_press(Message::BackupRestore).style(iced::theme::Button::Destructive));
+            } else {
+                backup_row = backup_row.push(show);
+            }
+            settings = settings.push(backup_row);
+            if self.backup_selected != Some(i) {
+                continue;
+            }
+            for (path, diff) in &self.backup_diff { //only the changed lines, a whole hyprland.conf would bury them
+                settings = settings.push(Text::new(path.clone()));
+                if !has_changes(diff) {
+                    settings = settings.push(Text::new(tr("Same as the current file")));
+                }
+                let mut lines = Column::new();
+                for line in diff {
+                    lines = match line {
+                        DiffLine::Same(..) => lines,
+                        DiffLine::Added(x) => lines.push(Text::new(format!("+ {x}")).style(TextStyle {color: style.application.success}.mk_theme())),
+                        DiffLine::Removed(x) => lines.push(Text::new(format!("- {x}")).style(TextStyle {color: style.application.danger}.mk_theme())),
+                    };
+                }
+                settings = settings.push(lines);
+            }
+        }
+        settings
+    }
+    pub fn preview_page(&self, style: ThemeCustom) -> Column<Message> {
+        let mut settings = Column::new().spacing(10);
+        let files = match &self.preview {
+            Some(files) => files,
+            None => return settings
+        };
+        let confirm = Button::new(Text::new(tr("Save"))).on_press(Message::PreviewConfirm).style(iced::theme::Button::Positive);
+        let cancel = Button::new(Text::new(tr("Cancel"))).on_press(Message::PreviewCancel);
+        let confirm_row = Row::new()
+            .push(Text::new(tr("Preview changes")))
+            .push(cancel)
+            .push(confirm)
+            .push(Text::new(tr("Enter or Y to save, Escape or N to cancel")))
+            .spacing(10);
+        settings = settings.push(confirm_row);
+        for i in 0..files.len() {
+            let file = &files[i];
+            let mut show = Button::new(Text::new(tr("Show Full File"))).on_press(Message::PreviewShowFile(i));
+            if self.preview_full == Some(i) {
+                show = show.style(style.secondary.mk_theme());
+            }
+            settings = settings.push(Row::new().push(Text::new(file.path.clone())).push(show).spacing(10));
+            if self.preview_full == Some(i) {
+                settings = settings.push(Text::new(file.contents.clone()));
+            } else if file.diff.len() == 0 {
+                settings = settings.push(Text::new(tr("Same as the current file")));
+            } else {
+                let mut lines = Column::new();
+                for line in file.diff.lines() {
+                    let mut text = Text::new(line.to_string());
+                    if line.starts_with('+') && !line.starts_with("+++") {
+                        text = text.style(TextStyle {color: style.application.success}.mk_theme());
+                    } else if line.starts_with('-') && !line.starts_with("---") {
+                        text = text.style(TextStyle {color: style.application.danger}.mk_theme());
+                    }
+                    lines = lines.push(text);
+                }
+                settings = settings.push(lines);
+            }
+        }
+        settings
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,60 @@
+use std::fs;
+use crate::lib_diff::unified_diff;
+use crate::lib_render::{check_hyprland, hyprland_path, render};
+use crate::Configurator;
+
+//The preview shown before a save, every file the save would write and how it differs from the one on disk
+
+#[derive(Debug, Clone)]
+pub struct FilePreview {
+    pub path: String,
+    pub contents: String, //what the save would write
+    pub diff: String, //unified diff against the file on disk, empty when nothing changes
+}
+
+impl Configurator {
+    pub fn open_preview(&mut self) {
+        if self.conflicts().len() > 0 || !self.unsaved { //try_save already knows what to do with these
+            self.try_save();
+            return;
+        }
+        let input = match self.render_input() {
+            Ok(input) => input,
+            Err(e) => {
+                self.errors = vec![e];
+                return;
+            }
+        };
+        let rendered = match render(&input) {
+            Ok(rendered) => rendered,
+            Err(e) => {
+                self.errors = vec![e];
+                return;
+            }
+        };
+        let path = hyprland_path(&input.home);
+        let problems = check_hyprland(&path, &rendered.files[&path]);
+        if problems.len() > 0 { //no point confirming a save that would be refused
+            self.errors = problems;
+            return;
+        }
+        let mut files = vec![];
+        for (path, contents) in rendered.files {
+            let current = fs::read_to_string(&path).unwrap_or_default(); //a missing file shows up as all new lines
+            files.push(FilePreview {
+                diff: unified_diff(&path, &current, &contents),
+                path,
+                contents,
+            });
+        }
+        self.preview = Some(files);
+        self.preview_full = None;
+    }
+    pub fn confirm_preview(&mut self) {
+        self.preview = None;
+        self.try_save();
+    }
+    pub fn cancel_preview(&mut self) {
+        self.preview = None;
+    }
+}
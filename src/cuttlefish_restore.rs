@@ -0,0 +1,58 @@
+use crate::lib_backup::{list_backups, restore_backup};
+use crate::lib_cfg::{get_cfg_data, get_home, CfgError};
+use crate::lib_diff::diff_lines;
+use crate::Configurator;
+
+//The restore page, lists the backups every save leaves behind and puts one back
+
+impl Configurator {
+    pub fn refresh_backups(&mut self) {
+        match get_home().and_then(|home| list_backups(&home)) {
+            Ok(backups) => self.backups = backups,
+            Err(e) => self.errors.push(e)
+        }
+        self.backup_selected = None;
+        self.backup_diff = vec![];
+        if self.current_page == crate::Page::Restore {
+            self.index_max = self.page_max(crate::Page::Restore);
+            if self.index > self.index_max {
+                self.index = self.index_max;
+            }
+        }
+    }
+    pub fn select_backup(&mut self, i: usize) { //diffs against what is on disk now, so the changes read as what the restore would do
+        self.backup_selected = Some(i);
+        self.backup_diff = vec![];
+        for (path, contents) in &self.backups[i].files {
+            let current = std::fs::read_to_string(path).unwrap_or_default();
+            self.backup_diff.push((path.clone(), diff_lines(&current, contents)));
+        }
+    }
+    pub fn restore_selected(&mut self) {
+        let i = match self.backup_selected {
+            Some(i) => i,
+            None => return
+        };
+        let home = match get_home() {
+            Ok(home) => home,
+            Err(e) => {
+                self.errors.push(e);
+                return;
+            }
+        };
+        let errors = restore_backup(&home, &self.backups[i]);
+        let restored = errors.iter().all(|x| matches!(x, CfgError::MissingTool(..)));
+        self.errors.extend(errors);
+        if restored { //the files are back, show the settings they hold
+            match get_cfg_data() {
+                Ok((data, warnings)) => {
+                    self.load_file_data(data);
+                    self.cfg_warnings = warnings;
+                    self.unsaved = false;
+                }
+                Err(e) => self.errors.push(e)
+            }
+        }
+        self.refresh_backups();
+    }
+}
@@ -0,0 +1,22 @@
+use crate::lib_cfg::WindowRule;
+use crate::lib_clients::{read_clients, rule_from_client};
+use crate::Configurator;
+
+//The window rules page, rules are kept in cfg.toml and written as windowrulev2 lines
+
+impl Configurator {
+    pub fn list_clients(&mut self) {
+        match read_clients() {
+            Ok(clients) => self.clients = clients,
+            Err(e) => self.errors.push(e)
+        }
+    }
+    pub fn add_rule_from_client(&mut self, i: usize) {
+        self.window_rules.push(rule_from_client(&self.clients[i]));
+        self.unsaved = true;
+    }
+    pub fn add_rule(&mut self) { //starts out unfinished, it is left out of hyprland.conf until it matches something
+        self.window_rules.push(WindowRule::default());
+        self.unsaved = true;
+    }
+}
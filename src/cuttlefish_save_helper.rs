@@ -1,183 +1,189 @@
-use toml::to_string;
-use std::fs;
-use std::process::Command;
-use oceania_style::{SelectedTheme, string_from_col};
+use oceania_style::{SelectedTheme, ThemeSet, string_from_col};
 use crate::lib_cfg::*;
+use crate::lib_render::{Palette, RenderInput, check_hyprland, hyprland_path, render, write_rendered};
+use crate::lib_backup::take_backup;
+use crate::lib_import::{import_hyprland, usercfg_with};
+use crate::cuttlefish_animations::bezier_text;
+use rfd::FileDialog;
 
 
-use crate::Configurator;
+use crate::{Configurator, Page};
 impl Configurator {
-    pub fn mk_config(&self) {
-        //self-cfg
-        {
-        let home = get_home();
-        let path = format!("{home}/Oceania/cfg.toml");
-        let backup_path = format!("{home}/Oceania");
-        let mut left_widgets = vec![];
-        let mut center_widgets = vec![];
-        let mut right_widgets = vec![];
-        for i in 0..self.bar_left.len() {
-            left_widgets.push(encode_widget(self.bar_left[i]))
-        }
-        for i in 0..self.bar_center.len() {
-            center_widgets.push(encode_widget(self.bar_center[i]))
+    pub fn conflicts(&self) -> Vec<BindConflict> {
+        find_conflicts(&self.binds, self.primary_key, self.secondary_key, &self.workspaces, &self.directions, &self.layout)
+    }
+    pub fn try_save(&mut self) { //refuse to write a config where two bindings fight over the same keys
+        if self.conflicts().len() > 0 {
+            self.current_page = Page::Bind;
+            self.index_max = self.page_max(Page::Bind);
+            if self.index > self.index_max {
+                self.index = self.index_max;
+            }
+            return;
         }
-        for i in 0..self.bar_right.len() {
-            right_widgets.push(encode_widget(self.bar_right[i]))
+        if self.unsaved {
+            self.errors = self.mk_config();
+            self.unsaved = self.errors.iter().any(|x| !matches!(x, CfgError::MissingTool(..))); //a missing tool only means nothing got reloaded, the files are written
+            if !self.unsaved {
+                self.pending_leftover = String::new(); //in usercfg.conf now
+            }
+            if let Ok(profiles) = list_profiles() { //saving may have created a profile
+                self.profiles = profiles;
+            }
+            self.refresh_backups();
         }
-        Command::new("mkdir").arg("-p").arg(backup_path).output().expect("uh oh");
-        let data = FileData {
-            theme: encode_theme(self.theme.clone()).to_string(),
-            primary: encode_pri(self.primary_key).to_string(),
-            secondary: encode_pri(self.secondary_key).to_string(),
+    }
+    pub fn file_data(&self) -> FileData { //the settings as they are stored in cfg.toml
+        FileData {
+            version: self.cfg_version.max(CFG_VERSION),
+            profile: self.profile.clone(),
+            theme: encode_theme(self.theme.clone()),
+            primary: self.primary_key.unwrap(),
+            secondary: self.secondary_key.unwrap(),
             wallpaper: self.wallpaper.clone(),
-            exit_h: encode_header(self.exit_header).to_string(),
-            exit_k: self.exit_key.clone(),
-            launch_h: encode_header(self.launch_header).to_string(),
-            launch_k: self.launch_key.clone(),
-            kill_h: encode_header(self.kill_header).to_string(),
-            kill_k: self.kill_key.clone(),
-            mini_h: encode_header(self.minimize_header).to_string(),
-            mini_k: self.minimize_key.clone(),
-            scratch_h: encode_header(self.scratch_header).to_string(),
-            scratch_k: self.scratch_key.clone(),
+            binds: self.binds.clone(),
             border: self.border.clone(),
-            win_anim: encode_win_anim(self.window_anim).to_string(),
-            work_anim: encode_work_anim(self.work_anim).to_string(),
-            blur: encode_blur(self.blur).to_string(),
-            widgets_left: left_widgets,
-            widgets_center: center_widgets,
-            widgets_right: right_widgets
-        };
-        let toml = to_string(&data).expect("failed to generate toml");
-        fs::write(path, toml).expect("failed to write cfg.toml");
+            win_anim: self.window_anim.unwrap(),
+            work_anim: self.work_anim.unwrap(),
+            animations: self.animations.clone(),
+            blur: self.blur,
+            widgets_left: self.bar_left.clone(),
+            widgets_center: self.bar_center.clone(),
+            widgets_right: self.bar_right.clone(),
+            workspaces: self.workspaces.clone(),
+            directions: self.directions.clone(),
+            layout: self.layout.clone(),
+            window_rules: self.window_rules.clone(),
+            monitors: self.monitors.clone(),
+            input: self.input.clone(),
+            autostart: self.autostart.clone(),
         }
-        //hyprland cfg
-        {
-            let home = get_home();
-            let data;
-            let pri_k = rip_shortcut(self.primary_key);
-            let sec_k = rip_shortcut(self.secondary_key);
-            let exit_h = rip_bind(self.exit_header, self.primary_key, self.secondary_key);
-            let exit_k = &self.exit_key;
-            let launch_h = rip_bind(self.launch_header, self.primary_key, self.secondary_key);
-            let launch_k = &self.launch_key;
-            let kill_h = rip_bind(self.kill_header, self.primary_key, self.secondary_key);
-            let kill_k = &self.kill_key;
-            let mini_h = rip_bind(self.minimize_header, self.primary_key, self.secondary_key);
-            let mini_k = &self.minimize_key;
-            let scratch_h = rip_bind(self.scratch_header, self.primary_key, self.secondary_key);
-            let scratch_k = &self.scratch_key;
-            let gaps = self.border.gaps;
-            let width = self.border.width;
-            let radius = self.border.radius;
-            let win_anim = rip_win_anim(self.window_anim);
-            let work_anim = rip_work_anim(self.work_anim);
-            let blur = self.blur;
-            let active_border = string_from_col(match self.theme {
-                SelectedTheme::Light => &self.theme_set.light.application.primary,
-                SelectedTheme::Dark => &self.theme_set.dark.application.primary,
-                SelectedTheme::Custom => &self.theme_set.custom.application.primary
-            });
-            let sector_head = r#"{"#;
-            let sector_tail = r#"}"#;
-            let path = format!("{home}/hypr/hyprland.conf");
-            data = format!("#AUTO-GENERATED CONFIG, DO NOT EDIT, CHANGES WILL BE OVERWRITTEN \n \
-    exec-once=oceania-shell\n \
-    exec-once={home}/hypr/autostart\n \
-    bind={exit_h},{exit_k},exec,wlogout\n \
-    bind={launch_h},{launch_k},exec,rofi -show drun\n \
-    bind={kill_h},{kill_k},killactive\n \
-    bind={mini_h},{mini_k},movetoworkspace,special\n \
-    bind={scratch_h},{scratch_k},togglespecialworkspace\n \
-    bind = {pri_k}, left, movefocus, l\n \
-    bind = {pri_k}, right, movefocus, r\n \
-    bind = {pri_k}, up, movefocus, u\n \
-    bind = {pri_k}, down, movefocus, d\n \
-    bind = {pri_k}_{sec_k}, left, movewindow, l\n \
-    bind = {pri_k}_{sec_k}, right, movewindow, r\n \
-    bind = {pri_k}_{sec_k}, up, movewindow, u\n \
-    bind = {pri_k}_{sec_k}, down, movewindow, d\n \
-    bind = {pri_k},1, workspace, 1 \n \
-    bind = {pri_k},2, workspace, 2 \n \
-    bind = {pri_k},3, workspace, 3 \n \
-    bind = {pri_k},4, workspace, 4 \n \
-    bind = {pri_k},5, workspace, 5 \n \
-    bind = {pri_k},6, workspace, 6 \n \
-    bind = {pri_k},7, workspace, 7 \n \
-    bind = {pri_k},8, workspace, 8 \n \
-    bind = {pri_k},9, workspace, 9 \n \
-    bind = {pri_k},0, workspace, 10 \n \
-    bind = {pri_k}_{sec_k},1,movetoworkspacesilent,1 \n \
-    bind = {pri_k}_{sec_k},2,movetoworkspacesilent,2 \n \
-    bind = {pri_k}_{sec_k},3,movetoworkspacesilent,3 \n \
-    bind = {pri_k}_{sec_k},4,movetoworkspacesilent,4 \n \
-    bind = {pri_k}_{sec_k},5,movetoworkspacesilent,5 \n \
-    bind = {pri_k}_{sec_k},6,movetoworkspacesilent,6 \n \
-    bind = {pri_k}_{sec_k},7,movetoworkspacesilent,7 \n \
-    bind = {pri_k}_{sec_k},8,movetoworkspacesilent,8 \n \
-    bind = {pri_k}_{sec_k},9,movetoworkspacesilent,9 \n \
-    bind = {pri_k}_{sec_k},0,movetoworkspacesilent,10 \n \
-    general {sector_head}\n \
-    gaps_in = {gaps}\n \
-    gaps_out = {gaps}\n \
-    border_size = {width}\n \
-    col.active_border = rgb({active_border})
-    {sector_tail}\n \
-    decoration {sector_head}\n \
-    rounding = {radius}\n \
-    blur {sector_head} \n \
-    enabled={blur} \n \
-    size=3 \n \
-    passes=3 \n \
-    new_optimizations=true \n \
-    {sector_tail}
-    {sector_tail}\n \
-    animations {sector_head}\n \
-    enabled = true\n \
-    animation = windows,{win_anim}\n \
-    animation = workspaces,{work_anim}\n \
-    {sector_tail}\n \
-    source={home}/hypr/usercfg.conf
-    ");
-            let user_cfg_path = format!("{home}/hypr/usercfg.conf");
-            let autostart_path = format!("{home}/hypr/autostart");
-            Command::new("touch")
-                .arg(user_cfg_path)
-                .arg(autostart_path.clone())
-                .output().expect("uh oh");
-            Command::new("chmod")
-                .arg("a+x")
-                .arg(autostart_path)
-                .output().expect("uh oh");
-            fs::write(path, data).expect("failed to write file");
-
-            Command::new("hyprctl")
-                .arg("reload")
-                .spawn()
-                .expect("oops, hyprctl failed, do you have Hyprland installed?");
+    }
+    pub fn load_file_data(&mut self, data: FileData) { //the reverse of file_data, for settings read after startup
+        self.theme = decode_theme(data.theme);
+        self.cfg_version = data.version;
+        self.profile = data.profile;
+        self.wallpaper = data.wallpaper;
+        self.primary_key = Some(data.primary);
+        self.secondary_key = Some(data.secondary);
+        self.binds = data.binds;
+        self.border = data.border;
+        self.window_anim = Some(data.win_anim);
+        self.work_anim = Some(data.work_anim);
+        self.bezier_inputs = data.animations.beziers.iter().map(bezier_text).collect();
+        self.bezier_names = data.animations.beziers.iter().map(|x| x.name.clone()).collect();
+        self.animations = data.animations;
+        self.blur = data.blur;
+        self.bar_left = data.widgets_left;
+        self.bar_center = data.widgets_center;
+        self.bar_right = data.widgets_right;
+        self.workspaces = data.workspaces;
+        self.directions = data.directions;
+        self.layout = data.layout;
+        self.window_rules = data.window_rules;
+        self.monitors = data.monitors;
+        self.input = data.input;
+        self.autostart = data.autostart;
+        self.index_max = self.page_max(self.current_page);
+        if self.index > self.index_max {
+            self.index = self.index_max;
         }
-        //waybar cfg
-        {
-            let left_widgets = rip_widget_vec(self.bar_left.clone());
-            let center_widgets = rip_widget_vec(self.bar_center.clone());
-            let right_widgets = rip_widget_vec(self.bar_right.clone());
-            println!("{left_widgets}\n{center_widgets}\n{right_widgets}")
+    }
+    pub fn switch_profile(&mut self, name: &str) { //load a profile and apply it through the normal save, preview first
+        match load_profile(name) {
+            Ok((data, warnings)) => {
+                self.load_file_data(data);
+                self.cfg_warnings = warnings;
+                self.unsaved = true;
+                self.open_preview();
+            }
+            Err(e) => self.errors.push(e)
+        }
+    }
+    pub fn save_profile_as(&mut self) {
+        let name = self.profile_input.trim().to_string();
+        if let Err(e) = check_profile_name(&name) {
+            self.errors.push(e);
+            return;
         }
-        //wallpaper set
-        {
-            let home = get_home();
-            Command::new("ln")
-                .arg("-sf")
-                .arg(self.wallpaper.clone())
-                .arg(format!("{home}/Oceania/wallpaper"))
-                .output()
-                .expect("oops!");
-            Command::new("swww")
-                .arg("img")
-                .arg(format!("{home}/Oceania/wallpaper"))
-                .spawn()
-                .expect("oops someone forgot to install swww");
+        self.profile = name;
+        self.profile_input = String::new();
+        self.unsaved = true;
+        self.open_preview();
+    }
+    pub fn import_prompt(&mut self) {
+        let home = match get_home() {
+            Ok(home) => home,
+            Err(e) => {
+                self.errors.push(e);
+                return;
+            }
+        };
+        if let Some(path) = FileDialog::new().set_directory(format!("{home}/hypr")).add_filter("hyprland.conf", &["conf"]).pick_file() {
+            self.import_conf(&path.to_string_lossy());
         }
     }
+    pub fn import_conf(&mut self, path: &str) { //nothing is written until the imported settings are saved, usercfg.conf included
+        match import_hyprland(path, self.file_data()) {
+            Ok(imported) => {
+                if !self.pending_leftover.contains(&imported.leftover) {
+                    self.pending_leftover.push_str(&imported.leftover);
+                }
+                self.load_file_data(imported.data);
+                self.cfg_warnings = imported.warnings;
+                self.unsaved = true;
+            }
+            Err(e) => self.errors.push(e)
+        }
+    }
+    pub fn palette(&self) -> Palette {
+        palette_of(&self.theme, &self.theme_set)
+    }
+    pub fn render_input(&self) -> Result<RenderInput, CfgError> {
+        let home = get_home()?;
+        Ok(RenderInput {
+            data: self.file_data(),
+            palette: self.palette(),
+            usercfg: usercfg_with(&home, &self.pending_leftover)?,
+            home,
+        })
+    }
+    pub fn mk_config(&self) -> Vec<CfgError> {
+        match self.render_input() {
+            Ok(input) => write_config(&input),
+            Err(e) => vec![e]
+        }
+    }
+}
+pub fn palette_of(theme: &SelectedTheme, set: &ThemeSet) -> Palette { //the colors a theme writes into the generated configs
+    let colors = match theme {
+        SelectedTheme::Light => &set.light.application,
+        SelectedTheme::Dark => &set.dark.application,
+        SelectedTheme::Custom => &set.custom.application
+    };
+    Palette {
+        background: string_from_col(&colors.background),
+        text: string_from_col(&colors.text),
+        primary: string_from_col(&colors.primary),
+        success: string_from_col(&colors.success),
+        danger: string_from_col(&colors.danger),
+    }
+}
+pub fn write_config(input: &RenderInput) -> Vec<CfgError> { //checks, backs up and writes every generated file, the gui and the cli share it
+    if input.data.version > CFG_VERSION { //a later build's cfg.toml, writing it back would drop what we can't read
+        return vec![CfgError::NewerVersion(input.data.version)];
+    }
+    let rendered = match render(input) {
+        Ok(rendered) => rendered,
+        Err(e) => return vec![e]
+    };
+    let path = hyprland_path(&input.home);
+    let problems = check_hyprland(&path, &rendered.files[&path]);
+    if problems.len() > 0 {
+        return problems;
+    }
+    if let Err(e) = take_backup(&input.home, &rendered.files) { //never overwrite what we couldn't copy
+        return vec![e];
+    }
+    write_rendered(&rendered)
 }
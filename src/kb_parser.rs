@@ -1,41 +1,110 @@
 use iced::keyboard::KeyCode;
 use oceania_style::SelectedTheme;
 
-use crate::{Configurator, CaptureInput, Page, lib_cfg::{ShortcutKey, BindKey, WindowAnimation, WorkAnimation, BarWidget}};
+use crate::{Configurator, CaptureInput, Page, lib_cfg::{ShortcutKey, BindKey, WindowAnimation, WorkAnimation, BarWidget, AutostartEntry, Keybind, Dispatcher, WorkspaceKeys, DirectionKeys, FollowMouse, LayoutEngine, MasterOrientation, AnimationName, BuiltInBind, built_in_clashes}};
 
 impl Configurator {
+    pub fn page_max(&self, page: Page) -> usize { //the index of the save button on each page
+        match page {
+            Page::Main => 6,
+            Page::Bind => self.binds.len() + 6,
+            Page::Work => 4,
+            Page::Layout => 5,
+            Page::Rules => 2,
+            Page::Monitors => 2,
+            Page::Input => 9,
+            Page::Bar => 8,
+            Page::Init => self.autostart.len() + 1,
+            Page::Anim => AnimationName::ALL.len() + 7,
+            Page::Restore => self.backups.len(),
+        }
+    }
+    pub fn push_autostart(&mut self) {
+        let command = self.autostart_input.trim().to_string();
+        if command.len() > 0 {
+            self.autostart.push(AutostartEntry {
+                command,
+                enabled: true,
+            });
+            self.autostart_input = String::new();
+            self.index_max = self.page_max(self.current_page);
+            self.unsaved = true;
+        }
+    }
+    pub fn marked_bind(&self) -> Option<usize> { //the keybinding under the cursor, if any
+        let i = self.index;
+        if self.current_page == Page::Bind && i >= 2 && i < self.binds.len() + 2 {
+            Some(i - 2)
+        } else {
+            None
+        }
+    }
+    pub fn push_bind(&mut self) {
+        self.binds.push(Keybind {
+            header: BindKey::PrimaryKey,
+            mods: vec![],
+            key: String::new(),
+            dispatcher: Dispatcher::Exec,
+            arg: String::new(),
+        });
+        self.index_max = self.page_max(self.current_page);
+        self.unsaved = true;
+    }
+    pub fn toggle_bind_mod(&mut self, i: usize, modifier: ShortcutKey) {
+        let mods = &mut self.binds[i].mods;
+        match mods.iter().position(|x| *x == modifier) {
+            Some(pos) => {
+                mods.remove(pos);
+            }
+            None => {
+                mods.push(modifier);
+            }
+        }
+        self.unsaved = true;
+    }
     pub fn kb_parse(&mut self, evt: iced::keyboard::Event) {
         match evt {
             iced::keyboard::Event::KeyPressed { key_code, modifiers} => { // code for handling key presses
                 match self.capture_next.as_ref().unwrap() { //check if next input should be captured
                     &CaptureInput::NoKey => { // if no captures are wanted, go through this parsing block
-                        if key_code == KeyCode::Up {
+                        if self.preview.is_some() { //while a save waits to be confirmed, only answer the preview
+                            if key_code == KeyCode::Enter || key_code == KeyCode::Y {
+                                self.confirm_preview();
+                            } else if key_code == KeyCode::Escape || key_code == KeyCode::N {
+                                self.cancel_preview();
+                            }
+                        } else if key_code == KeyCode::Z && iced::keyboard::Modifiers::control(modifiers) { //undo, or redo with shift held
+                            if iced::keyboard::Modifiers::shift(modifiers) {
+                                self.redo();
+                            } else {
+                                self.undo();
+                            }
+                        } else if key_code == KeyCode::Up {
                             if iced::keyboard::Modifiers::shift(modifiers) {//go up a page
                                 self.current_page = match self.current_page {
-                                    Page::Main => {
-                                        self.index_max = 1;
-                                        Page::Init
-                                    }
-                                    Page::Bind => {
-                                        self.index_max = 4;
-                                        Page::Main
-                                    }
-                                    Page::Anim => {
-                                        self.index_max = 7;
-                                        Page::Bind
-                                    }
-                                    Page::Bar => {
-                                        self.index_max = 6;
-                                        Page::Anim
-                                    }
-                                    Page::Init => {
-                                        self.index_max = 8;
-                                        Page::Bar
-                                    }
+                                    Page::Main => Page::Restore,
+                                    Page::Bind => Page::Main,
+                                    Page::Work => Page::Bind,
+                                    Page::Layout => Page::Work,
+                                    Page::Rules => Page::Layout,
+                                    Page::Monitors => Page::Rules,
+                                    Page::Input => Page::Monitors,
+                                    Page::Anim => Page::Input,
+                                    Page::Bar => Page::Anim,
+                                    Page::Init => Page::Bar,
+                                    Page::Restore => Page::Init,
                                 };
+                                self.index_max = self.page_max(self.current_page);
                                 if self.index > self.index_max {
                                     self.index = self.index_max;
                                 }
+                            } else if iced::keyboard::Modifiers::control(modifiers) && self.current_page == Page::Init { //move the marked autostart entry up
+                                let i = self.index;
+                                if i > 1 && i <= self.autostart.len() {
+                                    self.autostart.swap(i - 1, i - 2);
+                                    self.index = self.index - 1;
+                                    self.unsaved = true;
+                                }
                             } else { //move the mini cursor up
                                 if self.index != 0 {
                                     self.index = self.index -1;
@@ -44,40 +113,36 @@ impl Configurator {
                         } else if key_code == KeyCode::Down {
                             if iced::keyboard::Modifiers::shift(modifiers) {//go down a page
                                 self.current_page = match self.current_page {
-                                    Page::Main => {
-                                        self.index_max = 7;
-                                        Page::Bind
-                                    }
-                                    Page::Bind => {
-                                        self.index_max = 6;
-                                        Page::Anim
-                                    }
-                                    Page::Anim => {
-                                        self.index_max = 8;
-                                        Page::Bar
-                                    }
-                                    Page::Bar => {
-                                        self.index_max = 1;
-                                        Page::Init
-                                    }
-                                    Page::Init => {
-                                        self.index_max = 4;
-                                        Page::Main
-                                    }
-                               };
-                               if self.index > self.index_max {
+                                    Page::Main => Page::Bind,
+                                    Page::Bind => Page::Work,
+                                    Page::Work => Page::Layout,
+                                    Page::Layout => Page::Rules,
+                                    Page::Rules => Page::Monitors,
+                                    Page::Monitors => Page::Input,
+                                    Page::Input => Page::Anim,
+                                    Page::Anim => Page::Bar,
+                                    Page::Bar => Page::Init,
+                                    Page::Init => Page::Restore,
+                                    Page::Restore => Page::Main,
+                                };
+                                self.index_max = self.page_max(self.current_page);
+                                if self.index > self.index_max {
                                     self.index = self.index_max;
                                 }
+                            } else if iced::keyboard::Modifiers::control(modifiers) && self.current_page == Page::Init { //move the marked autostart entry down
+                                let i = self.index;
+                                if i >= 1 && i < self.autostart.len() {
+                                    self.autostart.swap(i - 1, i);
+                                    self.index = self.index + 1;
+                                    self.unsaved = true;
+                                }
                             } else { //move the mini cursor down
                                 if self.index < self.index_max {
                                     self.index = self.index +1;
                                 }
                             }
-                        } else if key_code == KeyCode::S { //save
-                            if self.unsaved {
-                                self.mk_config();
-                            }
-                            self.unsaved = false;
+                        } else if key_code == KeyCode::S { //save, once the preview is confirmed
+                            self.open_preview();
                         } else if key_code == KeyCode::Enter { // if the enter key is pressed, interact with certain widgets
                             match self.current_page {
                                 Page::Main => {
@@ -88,19 +153,32 @@ impl Configurator {
                                             SelectedTheme::Custom => SelectedTheme::Light,
                                         };
                                         self.unsaved = true;
+                                    } else if self.index == 4 && self.profiles.len() > 0 { // switch to the next profile
+                                        let next = match self.profiles.iter().position(|x| *x == self.profile) {
+                                            Some(i) => (i + 1) % self.profiles.len(),
+                                            None => 0
+                                        };
+                                        let name = self.profiles[next].clone();
+                                        self.switch_profile(&name);
+                                    } else if self.index == 5 {
+                                        self.import_prompt();
                                     }
                                 }
                                 Page::Bind => { // set the captures if needed
-                                    if self.index == 2 {
-                                        self.capture_next = Some(CaptureInput::ExitKey);
-                                    } else if self.index == 3 {
-                                        self.capture_next = Some(CaptureInput::LaunchKey);
-                                    } else if self.index == 4 {
-                                        self.capture_next = Some(CaptureInput::KillKey);
-                                    } else if self.index == 5 {
-                                        self.capture_next = Some(CaptureInput::MiniKey);
-                                    } else if self.index == 6 {
-                                        self.capture_next = Some(CaptureInput::ScratchKey);
+                                    if let Some(i) = self.marked_bind() {
+                                        self.capture_next = Some(CaptureInput::Bind(i));
+                                    } else if self.index == self.binds.len() + 2 {
+                                        self.push_bind();
+                                    } else if self.index == self.binds.len() + 3 { //the next set of direction keys
+                                        let pos = DirectionKeys::ALL.iter().position(|x| *x == self.directions.keys).unwrap();
+                                        self.directions.keys = DirectionKeys::ALL[(pos + 1) % DirectionKeys::ALL.len()];
+                                        self.unsaved = true;
+                                    } else if self.index == self.binds.len() + 4 {
+                                        self.directions.resize = !self.directions.resize;
+                                        self.unsaved = true;
+                                    } else if self.index == self.binds.len() + 5 {
+                                        self.directions.submap = !self.directions.submap;
+                                        self.unsaved = true;
                                     }
                                 }
                                 Page::Bar => {
@@ -123,21 +201,85 @@ impl Configurator {
                                         self.unsaved = true;
                                     }
                                 }
-                                Page::Init => {
-
+                                Page::Init => { //add the typed command, or flip the marked entry on and off
+                                    let i = self.index;
+                                    if i == 0 {
+                                        self.push_autostart();
+                                    } else if i <= self.autostart.len() {
+                                        self.autostart[i - 1].enabled = !self.autostart[i - 1].enabled;
+                                        self.unsaved = true;
+                                    }
+                                }
+                                Page::Work => { //flip the marked toggle
+                                    if self.index == 2 {
+                                        self.workspaces.follow = !self.workspaces.follow;
+                                        self.unsaved = true;
+                                    } else if self.index == 3 {
+                                        self.workspaces.cycle = !self.workspaces.cycle;
+                                        self.unsaved = true;
+                                    }
+                                }
+                                Page::Layout => { //flip the marked toggle
+                                    if self.index == 1 {
+                                        self.layout.pseudotile = !self.layout.pseudotile;
+                                        self.unsaved = true;
+                                    } else if self.index == 2 {
+                                        self.layout.preserve_split = !self.layout.preserve_split;
+                                        self.unsaved = true;
+                                    } else if self.index == 4 {
+                                        self.layout.new_is_master = !self.layout.new_is_master;
+                                        self.unsaved = true;
+                                    }
                                 }
-                                Page::Anim => {//toggle blur if relevant
+                                Page::Rules => {
+                                    if self.index == 0 {
+                                        self.list_clients();
+                                    } else if self.index == 1 {
+                                        self.add_rule();
+                                    }
+                                }
+                                Page::Monitors => {
+                                    if self.index == 0 {
+                                        self.detect_monitors();
+                                    } else if self.index == 1 {
+                                        self.arrange_monitors();
+                                    }
+                                }
+                                Page::Input => { //flip the marked toggle
+                                    if self.index == 5 {
+                                        self.input.natural_scroll = !self.input.natural_scroll;
+                                        self.unsaved = true;
+                                    } else if self.index == 6 {
+                                        self.input.touchpad_natural_scroll = !self.input.touchpad_natural_scroll;
+                                        self.unsaved = true;
+                                    } else if self.index == 7 {
+                                        self.input.tap_to_click = !self.input.tap_to_click;
+                                        self.unsaved = true;
+                                    }
+                                }
+                                Page::Anim => {//toggle blur or the marked animation if relevant
+                                    let i = self.index;
                                     if self.index == 5 {
                                         self.blur = !self.blur;
                                         self.unsaved = true;
+                                    } else if i >= 7 && i < AnimationName::ALL.len() + 7 {
+                                        let name = AnimationName::ALL[i - 7];
+                                        self.set_anim_enabled(name, !self.animations.entry(name).enabled);
+                                    }
+                                }
+                                Page::Restore => { //the first press shows the changes, the second restores
+                                    let i = self.index;
+                                    if i < self.backups.len() {
+                                        if self.backup_selected == Some(i) {
+                                            self.restore_selected();
+                                        } else {
+                                            self.select_backup(i);
+                                        }
                                     }
                                 }
                             }
                             if self.index == self.index_max {
-                                if self.unsaved {
-                                    self.mk_config();
-                                }
-                                self.unsaved = false;
+                                self.open_preview();
                             }
                         } else if key_code == KeyCode::Key1 {//dropdown management with number keys
                             if self.current_page == Page::Main {
@@ -155,20 +297,11 @@ impl Configurator {
                                 } else if self.index == 1 {
                                     self.secondary_key = Some(ShortcutKey::Super);
                                     self.unsaved = true;
-                                } else if self.index == 2 {
-                                    self.exit_header = Some(BindKey::PrimaryKey);
-                                    self.unsaved = true;
-                                } else if self.index == 3 {
-                                    self.launch_header = Some(BindKey::PrimaryKey);
-                                    self.unsaved = true;
-                                } else if self.index == 4 {
-                                    self.kill_header = Some(BindKey::PrimaryKey);
-                                    self.unsaved = true;
-                                } else if self.index == 5 {
-                                    self.minimize_header = Some(BindKey::PrimaryKey);
+                                } else if let Some(i) = self.marked_bind() {
+                                    self.binds[i].header = BindKey::PrimaryKey;
                                     self.unsaved = true;
-                                } else if self.index == 6 {
-                                    self.scratch_header = Some(BindKey::PrimaryKey);
+                                } else if self.index == self.binds.len() + 3 {
+                                    self.directions.keys = DirectionKeys::Arrows;
                                     self.unsaved = true;
                                 }
                             } else if self.current_page == Page::Anim {
@@ -179,6 +312,21 @@ impl Configurator {
                                     self.work_anim = Some(WorkAnimation::None);
                                     self.unsaved = true;
                                 }
+                            } else if self.current_page == Page::Work {
+                                if self.index == 1 {
+                                    self.workspaces.keys = WorkspaceKeys::Numbers;
+                                    self.unsaved = true;
+                                }
+                            } else if self.current_page == Page::Layout {
+                                if self.index == 0 {
+                                    self.layout.engine = LayoutEngine::Dwindle;
+                                    self.unsaved = true;
+                                }
+                            } else if self.current_page == Page::Input {
+                                if self.index == 8 {
+                                    self.input.follow_mouse = FollowMouse::ALL[0];
+                                    self.unsaved = true;
+                                }
                             } else if self.current_page == Page::Bar {
                                 if self.index == 0 {
                                     self.next_widget = Some(BarWidget::Audio);
@@ -208,20 +356,11 @@ impl Configurator {
                                 } else if self.index == 1 {
                                     self.secondary_key = Some(ShortcutKey::Alt);
                                     self.unsaved = true;
-                                } else if self.index == 2 {
-                                    self.exit_header = Some(BindKey::SecondaryKey);
+                                } else if let Some(i) = self.marked_bind() {
+                                    self.binds[i].header = BindKey::SecondaryKey;
                                     self.unsaved = true;
-                                } else if self.index == 3 {
-                                    self.launch_header = Some(BindKey::SecondaryKey);
-                                    self.unsaved = true;
-                                } else if self.index == 4 {
-                                    self.kill_header = Some(BindKey::SecondaryKey);
-                                    self.unsaved = true;
-                                } else if self.index == 5 {
-                                    self.minimize_header = Some(BindKey::SecondaryKey);
-                                    self.unsaved = true;
-                                } else if self.index == 6 {
-                                    self.scratch_header = Some(BindKey::SecondaryKey);
+                                } else if self.index == self.binds.len() + 3 {
+                                    self.directions.keys = DirectionKeys::Vim;
                                     self.unsaved = true;
                                 }
                             } else if self.current_page == Page::Anim {
@@ -232,6 +371,21 @@ impl Configurator {
                                     self.work_anim = Some(WorkAnimation::Slide);
                                     self.unsaved = true;
                                 }
+                            } else if self.current_page == Page::Work {
+                                if self.index == 1 {
+                                    self.workspaces.keys = WorkspaceKeys::Function;
+                                    self.unsaved = true;
+                                }
+                            } else if self.current_page == Page::Layout {
+                                if self.index == 0 {
+                                    self.layout.engine = LayoutEngine::Master;
+                                    self.unsaved = true;
+                                }
+                            } else if self.current_page == Page::Input {
+                                if self.index == 8 {
+                                    self.input.follow_mouse = FollowMouse::ALL[1];
+                                    self.unsaved = true;
+                                }
                             } else if self.current_page == Page::Bar {
                                 if self.index == 0 {
                                     self.next_widget = Some(BarWidget::Backlight);
@@ -261,20 +415,11 @@ impl Configurator {
                                 } else if self.index == 1 {
                                     self.secondary_key = Some(ShortcutKey::Shift);
                                     self.unsaved = true;
-                                } else if self.index == 2 {
-                                    self.exit_header = Some(BindKey::BothKey);
+                                } else if let Some(i) = self.marked_bind() {
+                                    self.binds[i].header = BindKey::BothKey;
                                     self.unsaved = true;
-                                } else if self.index == 3 {
-                                    self.launch_header = Some(BindKey::BothKey);
-                                    self.unsaved = true;
-                                } else if self.index == 4 {
-                                    self.kill_header = Some(BindKey::BothKey);
-                                    self.unsaved = true;
-                                } else if self.index == 5 {
-                                    self.minimize_header = Some(BindKey::BothKey);
-                                    self.unsaved = true;
-                                } else if self.index == 6 {
-                                    self.scratch_header = Some(BindKey::BothKey);
+                                } else if self.index == self.binds.len() + 3 {
+                                    self.directions.keys = DirectionKeys::Wasd;
                                     self.unsaved = true;
                                 }
                             } else if self.current_page == Page::Anim {
@@ -285,6 +430,16 @@ impl Configurator {
                                     self.work_anim = Some(WorkAnimation::SlideVert);
                                     self.unsaved = true;
                                 }
+                            } else if self.current_page == Page::Work {
+                                if self.index == 1 {
+                                    self.workspaces.keys = WorkspaceKeys::Numpad;
+                                    self.unsaved = true;
+                                }
+                            } else if self.current_page == Page::Input {
+                                if self.index == 8 {
+                                    self.input.follow_mouse = FollowMouse::ALL[2];
+                                    self.unsaved = true;
+                                }
                             } else if self.current_page == Page::Bar {
                                 if self.index == 0 {
                                     self.next_widget = Some(BarWidget::Battery);
@@ -314,12 +469,20 @@ impl Configurator {
                                 } else if self.index == 1 {
                                     self.secondary_key = Some(ShortcutKey::Ctrl);
                                     self.unsaved = true;
+                                } else if let Some(i) = self.marked_bind() {
+                                    self.binds[i].header = BindKey::NoKey;
+                                    self.unsaved = true;
                                 }
                             } else if self.current_page == Page::Anim {
                                 if self.index == 4 {
                                     self.work_anim = Some(WorkAnimation::Fade);
                                     self.unsaved = true;
                                 }
+                            } else if self.current_page == Page::Input {
+                                if self.index == 8 {
+                                    self.input.follow_mouse = FollowMouse::ALL[3];
+                                    self.unsaved = true;
+                                }
                             }
                         } else if key_code == KeyCode::Right {//increment values with right presses
                             if self.current_page == Page::Anim {
@@ -334,7 +497,62 @@ impl Configurator {
                                     self.unsaved = true;
                                 } else if self.index == 5 {
                                     self.blur = !self.blur;
+                                } else if self.index == 6 {
+                                    self.step_popin(true);
+                                } else if self.index >= 7 && self.index < AnimationName::ALL.len() + 7 {
+                                    self.step_anim_speed(AnimationName::ALL[self.index - 7], true);
+                                }
+                            } else if self.current_page == Page::Work {
+                                if self.index == 0 && self.workspaces.count() < self.workspaces.keys.keys().len() {
+                                    self.workspaces.count = self.workspaces.count() as u8 + 1;
+                                    self.unsaved = true;
                                 }
+                            } else if self.current_page == Page::Layout {
+                                if self.index == 0 {
+                                    let pos = LayoutEngine::ALL.iter().position(|x| *x == self.layout.engine).unwrap();
+                                    self.layout.engine = LayoutEngine::ALL[(pos + 1) % LayoutEngine::ALL.len()];
+                                    self.unsaved = true;
+                                } else if self.index == 1 {
+                                    self.layout.pseudotile = !self.layout.pseudotile;
+                                    self.unsaved = true;
+                                } else if self.index == 2 {
+                                    self.layout.preserve_split = !self.layout.preserve_split;
+                                    self.unsaved = true;
+                                } else if self.index == 3 {
+                                    let pos = MasterOrientation::ALL.iter().position(|x| *x == self.layout.orientation).unwrap();
+                                    self.layout.orientation = MasterOrientation::ALL[(pos + 1) % MasterOrientation::ALL.len()];
+                                    self.unsaved = true;
+                                } else if self.index == 4 {
+                                    self.layout.new_is_master = !self.layout.new_is_master;
+                                    self.unsaved = true;
+                                }
+                            } else if self.current_page == Page::Input {
+                                if self.index == 0 {
+                                    self.cycle_kb_layout(true);
+                                } else if self.index == 1 {
+                                    self.cycle_kb_variant(true);
+                                } else if self.index == 2 {
+                                    self.step_repeat_rate(true);
+                                } else if self.index == 3 {
+                                    self.step_repeat_delay(true);
+                                } else if self.index == 4 {
+                                    self.step_sensitivity(true);
+                                } else if self.index == 5 {
+                                    self.input.natural_scroll = !self.input.natural_scroll;
+                                    self.unsaved = true;
+                                } else if self.index == 6 {
+                                    self.input.touchpad_natural_scroll = !self.input.touchpad_natural_scroll;
+                                    self.unsaved = true;
+                                } else if self.index == 7 {
+                                    self.input.tap_to_click = !self.input.tap_to_click;
+                                    self.unsaved = true;
+                                } else if self.index == 8 {
+                                    self.cycle_follow_mouse(true);
+                                }
+                            } else if let Some(i) = self.marked_bind() { //cycle through the dispatchers
+                                let pos = Dispatcher::ALL.iter().position(|x| *x == self.binds[i].dispatcher).unwrap();
+                                self.binds[i].dispatcher = Dispatcher::ALL[(pos + 1) % Dispatcher::ALL.len()];
+                                self.unsaved = true;
                             }
                         } else if key_code == KeyCode::Left { // decrement values with left presses
                             if self.current_page == Page::Anim {
@@ -349,7 +567,62 @@ impl Configurator {
                                     self.unsaved = true;
                                 } else if self.index == 5 {
                                     self.blur = !self.blur;
+                                } else if self.index == 6 {
+                                    self.step_popin(false);
+                                } else if self.index >= 7 && self.index < AnimationName::ALL.len() + 7 {
+                                    self.step_anim_speed(AnimationName::ALL[self.index - 7], false);
                                 }
+                            } else if self.current_page == Page::Work {
+                                if self.index == 0 && self.workspaces.count() > 1 {
+                                    self.workspaces.count = self.workspaces.count() as u8 - 1;
+                                    self.unsaved = true;
+                                }
+                            } else if self.current_page == Page::Layout {
+                                if self.index == 0 {
+                                    let pos = LayoutEngine::ALL.iter().position(|x| *x == self.layout.engine).unwrap();
+                                    self.layout.engine = LayoutEngine::ALL[(pos + 1) % LayoutEngine::ALL.len()];
+                                    self.unsaved = true;
+                                } else if self.index == 1 {
+                                    self.layout.pseudotile = !self.layout.pseudotile;
+                                    self.unsaved = true;
+                                } else if self.index == 2 {
+                                    self.layout.preserve_split = !self.layout.preserve_split;
+                                    self.unsaved = true;
+                                } else if self.index == 3 {
+                                    let pos = MasterOrientation::ALL.iter().position(|x| *x == self.layout.orientation).unwrap();
+                                    self.layout.orientation = MasterOrientation::ALL[(pos + MasterOrientation::ALL.len() - 1) % MasterOrientation::ALL.len()];
+                                    self.unsaved = true;
+                                } else if self.index == 4 {
+                                    self.layout.new_is_master = !self.layout.new_is_master;
+                                    self.unsaved = true;
+                                }
+                            } else if self.current_page == Page::Input {
+                                if self.index == 0 {
+                                    self.cycle_kb_layout(false);
+                                } else if self.index == 1 {
+                                    self.cycle_kb_variant(false);
+                                } else if self.index == 2 {
+                                    self.step_repeat_rate(false);
+                                } else if self.index == 3 {
+                                    self.step_repeat_delay(false);
+                                } else if self.index == 4 {
+                                    self.step_sensitivity(false);
+                                } else if self.index == 5 {
+                                    self.input.natural_scroll = !self.input.natural_scroll;
+                                    self.unsaved = true;
+                                } else if self.index == 6 {
+                                    self.input.touchpad_natural_scroll = !self.input.touchpad_natural_scroll;
+                                    self.unsaved = true;
+                                } else if self.index == 7 {
+                                    self.input.tap_to_click = !self.input.tap_to_click;
+                                    self.unsaved = true;
+                                } else if self.index == 8 {
+                                    self.cycle_follow_mouse(false);
+                                }
+                            } else if let Some(i) = self.marked_bind() {
+                                let pos = Dispatcher::ALL.iter().position(|x| *x == self.binds[i].dispatcher).unwrap();
+                                self.binds[i].dispatcher = Dispatcher::ALL[(pos + Dispatcher::ALL.len() - 1) % Dispatcher::ALL.len()];
+                                self.unsaved = true;
                             }
                         } else if key_code == KeyCode::Backspace {
                             if self.current_page == Page::Bar {
@@ -369,33 +642,37 @@ impl Configurator {
                                     }
                                     self.unsaved = true;
                                 }
+                            } else if self.current_page == Page::Init {
+                                let i = self.index;
+                                if i >= 1 && i <= self.autostart.len() {
+                                    self.autostart.remove(i - 1);
+                                    self.index_max = self.page_max(Page::Init);
+                                    if self.index > self.index_max {
+                                        self.index = self.index_max;
+                                    }
+                                    self.unsaved = true;
+                                }
+                            } else if let Some(i) = self.marked_bind() {
+                                self.binds.remove(i);
+                                self.index_max = self.page_max(Page::Bind);
+                                self.unsaved = true;
                             }
                         }
                     } 
-                    &CaptureInput::ExitKey => {
-                        self.exit_key = format!("{:?}", key_code);
-                        self.capture_next = Some(CaptureInput::NoKey);
-                        self.unsaved = true;
-                    }
-                    &CaptureInput::LaunchKey => {
-                        self.launch_key = format!("{:?}", key_code);
-                        self.capture_next = Some(CaptureInput::NoKey);
-                        self.unsaved = true;
-                    }
-                    &CaptureInput::KillKey => {
-                        self.kill_key = format!("{:?}", key_code);
+                    &CaptureInput::Bind(i) => {
+                        self.binds[i].key = format!("{:?}", key_code);
                         self.capture_next = Some(CaptureInput::NoKey);
                         self.unsaved = true;
                     }
-                    &CaptureInput::MiniKey => {
-                        self.minimize_key = format!("{:?}", key_code);
-                        self.capture_next = Some(CaptureInput::NoKey);
-                        self.unsaved = true;
-                    }
-                    &CaptureInput::ScratchKey => {
-                        self.scratch_key = format!("{:?}", key_code);
-                        self.capture_next = Some(CaptureInput::NoKey);
-                        self.unsaved = true;
+                    &CaptureInput::SubmapKey => { //a key one of our own bindings already uses is refused, the button keeps waiting for another
+                        let mut directions = self.directions.clone();
+                        directions.submap_key = format!("{:?}", key_code);
+                        let clashes = built_in_clashes(self.primary_key, self.secondary_key, &self.workspaces, &directions, &self.layout);
+                        if !clashes.iter().any(|x| x.0 == BuiltInBind::ResizeMode || x.1 == BuiltInBind::ResizeMode) {
+                            self.directions = directions;
+                            self.capture_next = Some(CaptureInput::NoKey);
+                            self.unsaved = true;
+                        }
                     }
                 }
             }
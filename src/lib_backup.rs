@@ -0,0 +1,169 @@
+#![allow(dead_code)]
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::time::{SystemTime, UNIX_EPOCH};
+use serde_derive::{Deserialize, Serialize};
+use crate::lib_cfg::{CfgError, write_atomic};
+use crate::lib_render::{PostAction, run_actions, write_all_atomic};
+
+//Every save keeps a copy of the files it overwrites, so a bad save can be rolled back
+
+pub const BACKUP_LIMIT: usize = 10; //older backups are deleted once there are more than this
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Backup {
+    pub stamp: u64, //milliseconds since the epoch, also the file name
+    pub files: BTreeMap<String, String>, //path -> contents before the save
+}
+
+pub fn backup_dir(home: &str) -> String {
+    format!("{home}/Oceania/backups")
+}
+fn backup_path(home: &str, stamp: u64) -> String {
+    format!("{}/{stamp}.toml", backup_dir(home))
+}
+fn now_stamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|x| x.as_millis() as u64).unwrap_or(0)
+}
+pub fn take_backup(home: &str, files: &BTreeMap<String, String>) -> Result<Option<u64>, CfgError> { //copies every file that is about to change, returns the new backup's stamp if one was needed
+    let mut old = BTreeMap::new();
+    for (path, contents) in files {
+        match fs::read_to_string(path) {
+            Ok(current) => {
+                if current != *contents {
+                    old.insert(path.clone(), current);
+                }
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => {} //nothing to lose
+            Err(e) => return Err(CfgError::Io(path.clone(), e))
+        }
+    }
+    if old.len() == 0 {
+        return Ok(None);
+    }
+    let dir = backup_dir(home);
+    fs::create_dir_all(&dir).map_err(|e| CfgError::Io(dir.clone(), e))?;
+    let mut stamp = now_stamp();
+    while fs::metadata(backup_path(home, stamp)).is_ok() { //two saves within the same millisecond
+        stamp = stamp + 1;
+    }
+    let backup = Backup {
+        stamp,
+        files: old,
+    };
+    write_atomic(&backup_path(home, stamp), &toml::to_string(&backup).map_err(CfgError::Serialize)?)?;
+    rotate_backups(home)?;
+    Ok(Some(stamp))
+}
+pub fn list_backups(home: &str) -> Result<Vec<Backup>, CfgError> { //newest first
+    let dir = backup_dir(home);
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(CfgError::Io(dir, e))
+    };
+    let mut backups = vec![];
+    for entry in entries {
+        let path = entry.map_err(|e| CfgError::Io(dir.clone(), e))?.path();
+        if path.extension().and_then(|x| x.to_str()) != Some("toml") {
+            continue;
+        }
+        let contents = fs::read_to_string(&path).map_err(|e| CfgError::Io(path.display().to_string(), e))?;
+        if let Ok(backup) = toml::from_str::<Backup>(&contents) { //anything else isn't one of ours, leave it alone
+            backups.push(backup);
+        }
+    }
+    backups.sort_by(|a, b| b.stamp.cmp(&a.stamp));
+    Ok(backups)
+}
+fn rotate_backups(home: &str) -> Result<(), CfgError> {
+    let backups = list_backups(home)?;
+    for i in BACKUP_LIMIT..backups.len() {
+        let path = backup_path(home, backups[i].stamp);
+        fs::remove_file(&path).map_err(|e| CfgError::Io(path, e))?;
+    }
+    Ok(())
+}
+pub fn restore_files(home: &str, backup: &Backup) -> Result<(), CfgError> { //all of the files come back or none of them do
+    take_backup(home, &backup.files)?; //the restore itself can be rolled back too
+    write_all_atomic(&backup.files)
+}
+pub fn restore_backup(home: &str, backup: &Backup) -> Vec<CfgError> { //then reloads like a save would
+    if let Err(e) = restore_files(home, backup) {
+        return vec![e];
+    }
+    run_actions(&[
+        PostAction::Run(vec!["hyprctl".to_string(), "reload".to_string()]),
+        PostAction::Run(vec!["pkill".to_string(), "-SIGUSR2".to_string(), "waybar".to_string()]),
+    ])
+}
+pub fn format_stamp(stamp: u64) -> String { //UTC, we have no timezone database to go by
+    let secs = stamp / 1000;
+    let days = (secs / 86400) as i64;
+    let rem = secs % 86400;
+    let z = days + 719468; //civil_from_days, see howardhinnant.github.io/date_algorithms.html
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02} UTC", rem / 3600, rem % 3600 / 60, rem % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn saves_are_backed_up_and_restored() { //only hands the temp directory around, never sets HOME
+        let root = env::temp_dir().join(format!("cuttlefish-backup-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        let home = root.display().to_string();
+        let path = format!("{home}/hyprland.conf");
+        let mut files = BTreeMap::new();
+        files.insert(path.clone(), "first".to_string());
+        assert_eq!(take_backup(&home, &files).unwrap(), None); //nothing was overwritten
+        fs::write(&path, "first").unwrap();
+        assert_eq!(take_backup(&home, &files).unwrap(), None); //unchanged
+        for i in 0..BACKUP_LIMIT + 2 {
+            files.insert(path.clone(), format!("save {i}"));
+            assert!(take_backup(&home, &files).unwrap().is_some());
+            fs::write(&path, format!("save {i}")).unwrap();
+        }
+        let backups = list_backups(&home).unwrap();
+        assert_eq!(backups.len(), BACKUP_LIMIT);
+        assert_eq!(backups[0].files[&path], format!("save {}", BACKUP_LIMIT));
+
+        let oldest = backups.last().unwrap().clone();
+        restore_files(&home, &oldest).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), oldest.files[&path]);
+        assert_eq!(list_backups(&home).unwrap()[0].files[&path], format!("save {}", BACKUP_LIMIT + 1));
+        let _ = fs::remove_dir_all(&root);
+    }
+    #[test]
+    fn failed_restore_changes_nothing() { //the second file can't be replaced, so the first goes back as it was
+        let root = env::temp_dir().join(format!("cuttlefish-restore-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("b.conf/taken")).unwrap(); //a directory where a file should go
+        let first = root.join("a.conf").display().to_string();
+        fs::write(&first, "current").unwrap();
+        let mut files = BTreeMap::new();
+        files.insert(first.clone(), "restored".to_string());
+        files.insert(root.join("b.conf").display().to_string(), "restored".to_string());
+        assert!(write_all_atomic(&files).is_err());
+        assert_eq!(fs::read_to_string(&first).unwrap(), "current");
+        assert!(!root.join("a.conf.tmp").exists() && !root.join("b.conf.tmp").exists());
+        let _ = fs::remove_dir_all(&root);
+    }
+    #[test]
+    fn stamps_are_readable() {
+        assert_eq!(format_stamp(0), "1970-01-01 00:00:00 UTC");
+        assert_eq!(format_stamp(1_709_210_096_000), "2024-02-29 12:34:56 UTC");
+    }
+}
@@ -1,63 +1,453 @@
 #![allow(dead_code)]
+use serde::de::DeserializeOwned;
 use serde_derive::{Deserialize, Serialize};
-use toml::{self, from_str};
+use toml::{self, from_str, Table, Value};
 use std::fs::read_to_string;
+use std::io::{ErrorKind, Write};
 use std::env;
 use std::fs;
 use gettextrs::gettext as tr;
 use oceania_style::SelectedTheme;
 
 
-#[derive(Deserialize, Debug, Serialize)]
+#[derive(Deserialize, Debug, Serialize, Clone, PartialEq)]
+#[serde(default)] //anything missing from cfg.toml is taken from FileData::default()
 pub struct FileData {
-    pub theme: String,
-    pub primary: String,
+    pub version: u32,
+    pub profile: String, //the profile these settings are saved to as well, empty when there is none
+    pub theme: ThemeName,
+    pub primary: ShortcutKey,
     pub wallpaper: String,
-    pub secondary: String,
-    pub exit_h: String,
-    pub exit_k: String,
-    pub launch_h: String,
-    pub launch_k: String,
-    pub kill_h: String,
-    pub kill_k: String,
-    pub mini_h: String,
-    pub mini_k: String,
-    pub scratch_h: String,
-    pub scratch_k: String,
+    pub secondary: ShortcutKey,
+    pub binds: Vec<Keybind>,
     pub border: Border,
-    pub win_anim: String,
-    pub work_anim: String,
-    pub blur: String,
-    pub widgets_left: Vec<String>,
-    pub widgets_center: Vec<String>,
-    pub widgets_right:  Vec<String>,
+    pub win_anim: WindowAnimation,
+    pub work_anim: WorkAnimation,
+    pub animations: Animations, //speeds and curves, the two presets above pick the styles
+    #[serde(with = "yes_no")]
+    pub blur: bool,
+    pub widgets_left: Vec<BarWidget>,
+    pub widgets_center: Vec<BarWidget>,
+    pub widgets_right:  Vec<BarWidget>,
+    pub autostart: Vec<AutostartEntry>,
+    pub workspaces: Workspaces,
+    pub directions: Directions,
+    pub monitors: Vec<MonitorCfg>,
+    pub input: Input,
+    pub layout: Layout,
+    pub window_rules: Vec<WindowRule>,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CfgWarning { //a value in cfg.toml that could not be read and what was done about it
+    pub key: String,
+    pub message: String,
+}
+
+#[derive(Debug)]
+pub enum CfgError {
+    NoConfigDir, //neither XDG_CONFIG_HOME nor HOME is set
+    Parse(toml::de::Error),
+    Serialize(toml::ser::Error), //the settings could not be turned back into toml
+    Io(String, std::io::Error), //the path that failed and why
+    MissingTool(String), //an external program we call isn't installed
+    BadProfileName(String),
+    UnknownProfile(String),
+    Invalid(String, usize, String), //a generated file that failed its check, path, line number and what is wrong
+    BadOutput(String, String), //a program we ran printed something we couldn't read, the program and why
+    NewerVersion(u32), //cfg.toml is in a layout from a later build, saving would drop what this one doesn't know
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Border {
     pub width: i32,
     pub radius: i32,
     pub gaps: i32,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keybind { //a single user-defined binding, pressing header + mods + key runs dispatcher with arg
+    pub header: BindKey,
+    #[serde(default)]
+    pub mods: Vec<ShortcutKey>,
+    pub key: String,
+    pub dispatcher: Dispatcher,
+    #[serde(default)]
+    pub arg: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BindConflict { //a binding whose keys are already taken
+    pub index: Option<usize>, //None when two built-in bindings clash with each other
+    pub other: Option<usize>, //the clashing binding, None when it clashes with an inferred one
+    pub built_in: Vec<BuiltInBind>, //the inferred ones involved, one or both sides
+    pub suggestion: Option<String>,
+}
+
+impl BindConflict {
+    pub fn built_in_text(&self) -> String { //e.g. moving focus and resizing use the same keys
+        let names: Vec<String> = self.built_in.iter().map(|x| x.to_string()).collect();
+        format!("{} {}", names.join(&format!(" {} ", tr("and"))), tr("use the same keys"))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuiltInBind { //what the bindings mk_config writes on its own are for
+    Focus,
+    Move,
+    Resize,
+    ResizeMode,
+    Workspace,
+    MoveToWorkspace,
+    Cycle,
+    Layout
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutostartEntry {
+    pub command: String,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Workspaces {
+    pub count: u8, //capped at the number of keys in the row
+    pub keys: WorkspaceKeys,
+    pub follow: bool, //go along with a window moved to another workspace
+    pub cycle: bool, //next and previous workspace on period and comma and the mouse wheel
+    pub monitors: Vec<WorkspaceMonitor>,
+}
+impl Default for Workspaces {
+    fn default() -> Self {
+        Workspaces {
+            count: 10,
+            keys: WorkspaceKeys::Numbers,
+            follow: false,
+            cycle: false,
+            monitors: vec![],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceMonitor { //keeps a workspace on one output
+    pub workspace: u8,
+    pub monitor: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum WorkspaceKeys { //the row of keys that picks a workspace
+    #[default]
+    #[serde(rename = "numbers")]
+    Numbers,
+    #[serde(rename = "function")]
+    Function,
+    #[serde(rename = "numpad")]
+    Numpad
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Directions { //focus and move are always bound, resizing is up to the user
+    pub keys: DirectionKeys,
+    pub resize: bool, //the primary key, resize_mod and a direction grow or shrink the focused window
+    pub resize_mod: ShortcutKey,
+    pub submap: bool, //the primary key and submap_key start a resize mode where the bare direction keys resize, escape ends it
+    pub submap_key: String,
+}
+impl Default for Directions {
+    fn default() -> Self {
+        Directions {
+            keys: DirectionKeys::Arrows,
+            resize: false,
+            resize_mod: ShortcutKey::Ctrl,
+            submap: false,
+            submap_key: "R".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Layout { //the options of both engines are kept, so switching back and forth loses nothing
+    pub engine: LayoutEngine,
+    pub pseudotile: bool, //dwindle, the primary key and P toggle a window between its own size and its tile
+    pub preserve_split: bool, //dwindle, a split keeps its direction when the window is resized, the primary key and T flip it
+    pub orientation: MasterOrientation, //master, where the master window goes
+    pub new_is_master: bool, //master, new windows take the master spot, the primary key and M swap with it
+}
+impl Default for Layout {
+    fn default() -> Self {
+        Layout {
+            engine: LayoutEngine::Dwindle,
+            pseudotile: true,
+            preserve_split: true,
+            orientation: MasterOrientation::Left,
+            new_is_master: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LayoutEngine {
+    #[default]
+    #[serde(rename = "dwindle")]
+    Dwindle,
+    #[serde(rename = "master")]
+    Master
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MasterOrientation {
+    #[default]
+    #[serde(rename = "left")]
+    Left,
+    #[serde(rename = "right")]
+    Right,
+    #[serde(rename = "top")]
+    Top,
+    #[serde(rename = "bottom")]
+    Bottom,
+    #[serde(rename = "center")]
+    Center
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Input { //the input block, hyprland's own defaults apart from tap to click
+    pub kb_layout: String, //xkb layout code, like us
+    pub kb_variant: String, //empty for the layout's default
+    pub repeat_rate: i32, //repeats per second while a key is held
+    pub repeat_delay: i32, //milliseconds before a held key starts repeating
+    pub sensitivity: f64, //pointer speed from -1 to 1
+    pub natural_scroll: bool, //mice
+    pub touchpad_natural_scroll: bool,
+    pub tap_to_click: bool,
+    pub follow_mouse: FollowMouse,
+}
+impl Default for Input {
+    fn default() -> Self {
+        Input {
+            kb_layout: "us".to_string(),
+            kb_variant: String::new(),
+            repeat_rate: 25,
+            repeat_delay: 600,
+            sensitivity: 0.0,
+            natural_scroll: false,
+            touchpad_natural_scroll: false,
+            tap_to_click: true,
+            follow_mouse: FollowMouse::Always,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FollowMouse { //how the pointer moves keyboard focus, in hyprland's order
+    #[serde(rename = "never")]
+    Never,
+    #[default]
+    #[serde(rename = "always")]
+    Always,
+    #[serde(rename = "click")]
+    Click,
+    #[serde(rename = "separate")]
+    Separate
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WindowRule { //one windowrulev2 line, every condition that is set has to match
+    pub action: RuleAction,
+    pub arg: String, //what the action takes, like a workspace or a size, unused by the ones that take nothing
+    pub class: String, //a regex, empty matches any
+    pub title: String, //a regex, empty matches any
+    pub floating: RuleState,
+    pub xwayland: RuleState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RuleAction {
+    #[default]
+    #[serde(rename = "float")]
+    Float,
+    #[serde(rename = "workspace")]
+    Workspace,
+    #[serde(rename = "size")]
+    Size,
+    #[serde(rename = "move")]
+    Move,
+    #[serde(rename = "opacity")]
+    Opacity,
+    #[serde(rename = "pin")]
+    Pin,
+    #[serde(rename = "fullscreen")]
+    Fullscreen,
+    #[serde(rename = "idleinhibit")]
+    IdleInhibit
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RuleState { //a yes or no condition that can also be left out
+    #[default]
+    #[serde(rename = "any")]
+    Any,
+    #[serde(rename = "yes")]
+    Yes,
+    #[serde(rename = "no")]
+    No
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MonitorCfg { //one output's monitor= line
+    pub name: String, //as hyprctl monitors calls it, like DP-1
+    pub width: u32,
+    pub height: u32,
+    #[serde(default)]
+    pub refresh: f64, //0 lets hyprland pick
+    #[serde(default)]
+    pub x: i32,
+    #[serde(default)]
+    pub y: i32,
+    #[serde(default = "one")]
+    pub scale: f64,
+    #[serde(default)]
+    pub transform: Transform,
+}
+fn one() -> f64 {
+    1.0
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Transform { //rotation counter-clockwise, then whether the output is mirrored, numbered as hyprland does
+    #[default]
+    #[serde(rename = "normal")]
+    Normal,
+    #[serde(rename = "90")]
+    Rotate90,
+    #[serde(rename = "180")]
+    Rotate180,
+    #[serde(rename = "270")]
+    Rotate270,
+    #[serde(rename = "flipped")]
+    Flipped,
+    #[serde(rename = "flipped-90")]
+    Flipped90,
+    #[serde(rename = "flipped-180")]
+    Flipped180,
+    #[serde(rename = "flipped-270")]
+    Flipped270
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DirectionKeys { //the keys for left, right, up and down
+    #[default]
+    #[serde(rename = "arrows")]
+    Arrows,
+    #[serde(rename = "vim")]
+    Vim,
+    #[serde(rename = "wasd")]
+    Wasd
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ThemeName { //how SelectedTheme is stored in cfg.toml
+    #[default]
+    #[serde(rename = "light")]
+    Light,
+    #[serde(rename = "dark")]
+    Dark,
+    #[serde(rename = "custom")]
+    Custom
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum WindowAnimation {
+    #[serde(rename = "slide")]
     Slide,
+    #[serde(rename = "popin")]
     PopIn,
     #[default]
+    #[serde(rename = "none")]
     None
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Animations {
+    pub entries: Vec<AnimationCfg>, //at most one per AnimationName, a missing one takes its defaults
+    pub popin: u32, //the size a window pops in from, in percent
+    pub beziers: Vec<Bezier>,
+}
+impl Default for Animations {
+    fn default() -> Self {
+        Animations {
+            entries: AnimationName::ALL.iter().map(|x| AnimationCfg::new(*x)).collect(),
+            popin: 80,
+            beziers: vec![],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnimationCfg { //one animation= line
+    pub name: AnimationName,
+    #[serde(default = "yes")]
+    pub enabled: bool, //only used where switchable, the others follow their preset
+    pub speed: f64, //in tenths of a second, lower is faster
+    #[serde(default = "default_curve")]
+    pub curve: String, //default or the name of one of the beziers
+}
+fn yes() -> bool {
+    true
+}
+fn default_curve() -> String {
+    "default".to_string()
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bezier { //a curve for bezier=, the two control points of a cubic bezier from 0,0 to 1,1
+    pub name: String,
+    pub x0: f64,
+    pub y0: f64,
+    pub x1: f64,
+    pub y1: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AnimationName { //the animation trees we configure, in the order they are written
+    #[default]
+    #[serde(rename = "windows")]
+    Windows,
+    #[serde(rename = "windowsOut")]
+    WindowsOut,
+    #[serde(rename = "workspaces")]
+    Workspaces,
+    #[serde(rename = "specialWorkspace")]
+    SpecialWorkspace,
+    #[serde(rename = "border")]
+    Border,
+    #[serde(rename = "fade")]
+    Fade,
+    #[serde(rename = "layers")]
+    Layers
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum WorkAnimation {
+    #[serde(rename = "slide")]
     Slide,
+    #[serde(rename = "slidev")]
     SlideVert,
+    #[serde(rename = "fade")]
     Fade,
     #[default]
+    #[serde(rename = "none")]
     None
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 
 pub enum BarWidget {
     #[default]
@@ -68,46 +458,157 @@ pub enum BarWidget {
     Clock,
     CPU,
     Disk,
+    #[serde(rename = "Keyboard State")]
     KeyboardState,
     RAM,
     Network,
     Temperature,
+    #[serde(rename = "System Tray")]
     Tray,
     Taskbar,
     Workspaces,
+    #[serde(rename = "Current User")]
     User
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum ShortcutKey {
     #[default]
+    #[serde(rename = "super")]
     Super,
+    #[serde(rename = "alt")]
     Alt,
+    #[serde(rename = "shift")]
     Shift,
-    Ctrl
+    #[serde(rename = "control")]
+    Ctrl,
+    #[serde(rename = "mod3")]
+    Mod3, //usually Hyper on layouts that have one
+    #[serde(rename = "mod5")]
+    Mod5, //usually AltGr
+    #[serde(rename = "caps")]
+    Caps
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum BindKey {
     #[default]
+    #[serde(rename = "pri")]
     PrimaryKey,
+    #[serde(rename = "sec")]
     SecondaryKey,
-    BothKey
+    #[serde(rename = "both")]
+    BothKey,
+    #[serde(rename = "none")]
+    NoKey
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Dispatcher {
+    #[default]
+    #[serde(rename = "exec")]
+    Exec,
+    #[serde(rename = "killactive")]
+    KillActive,
+    #[serde(rename = "exit")]
+    Exit,
+    #[serde(rename = "togglefloating")]
+    ToggleFloating,
+    #[serde(rename = "fullscreen")]
+    Fullscreen,
+    #[serde(rename = "pin")]
+    Pin,
+    #[serde(rename = "pseudo")]
+    PseudoTile,
+    #[serde(rename = "centerwindow")]
+    CenterWindow,
+    #[serde(rename = "workspace")]
+    Workspace,
+    #[serde(rename = "movetoworkspace")]
+    MoveToWorkspace,
+    #[serde(rename = "movetoworkspacesilent")]
+    MoveToWorkspaceSilent,
+    #[serde(rename = "togglespecialworkspace")]
+    ToggleSpecialWorkspace,
+    #[serde(rename = "movefocus")]
+    MoveFocus,
+    #[serde(rename = "movewindow")]
+    MoveWindow,
+    #[serde(rename = "resizeactive")]
+    ResizeActive,
+    #[serde(rename = "submap")]
+    Submap,
+    #[serde(rename = "togglesplit")]
+    ToggleSplit,
+    #[serde(rename = "layoutmsg")]
+    LayoutMsg,
 }
 
 impl ShortcutKey {
-    pub const ALL: [ShortcutKey; 4] = [
+    pub const ALL: [ShortcutKey; 7] = [
         ShortcutKey::Super,
         ShortcutKey::Alt,
         ShortcutKey::Shift,
         ShortcutKey::Ctrl,
+        ShortcutKey::Mod3,
+        ShortcutKey::Mod5,
+        ShortcutKey::Caps,
+    ];
+}
+impl BarWidget {
+    pub const ALL: [BarWidget; 15] = [
+        BarWidget::Audio,
+        BarWidget::Backlight,
+        BarWidget::Battery,
+        BarWidget::Bluetooth,
+        BarWidget::Clock,
+        BarWidget::CPU,
+        BarWidget::Disk,
+        BarWidget::KeyboardState,
+        BarWidget::RAM,
+        BarWidget::Network,
+        BarWidget::Temperature,
+        BarWidget::Tray,
+        BarWidget::Taskbar,
+        BarWidget::Workspaces,
+        BarWidget::User
     ];
 }
 impl BindKey {
-    pub const ALL: [BindKey; 3] = [
+    pub const ALL: [BindKey; 4] = [
         BindKey::PrimaryKey,
         BindKey::SecondaryKey,
         BindKey::BothKey,
+        BindKey::NoKey,
+    ];
+}
+impl Dispatcher {
+    pub const ALL: [Dispatcher; 18] = [
+        Dispatcher::Exec,
+        Dispatcher::KillActive,
+        Dispatcher::Exit,
+        Dispatcher::ToggleFloating,
+        Dispatcher::Fullscreen,
+        Dispatcher::Pin,
+        Dispatcher::PseudoTile,
+        Dispatcher::CenterWindow,
+        Dispatcher::Workspace,
+        Dispatcher::MoveToWorkspace,
+        Dispatcher::MoveToWorkspaceSilent,
+        Dispatcher::ToggleSpecialWorkspace,
+        Dispatcher::MoveFocus,
+        Dispatcher::MoveWindow,
+        Dispatcher::ResizeActive,
+        Dispatcher::Submap,
+        Dispatcher::ToggleSplit,
+        Dispatcher::LayoutMsg,
+    ];
+}
+impl ThemeName {
+    pub const ALL: [ThemeName; 3] = [
+        ThemeName::Light,
+        ThemeName::Dark,
+        ThemeName::Custom
     ];
 }
 impl WindowAnimation {
@@ -125,6 +626,259 @@ impl WorkAnimation {
         WorkAnimation::Fade
     ];
 }
+impl AnimationName {
+    pub const ALL: [AnimationName; 7] = [
+        AnimationName::Windows,
+        AnimationName::WindowsOut,
+        AnimationName::Workspaces,
+        AnimationName::SpecialWorkspace,
+        AnimationName::Border,
+        AnimationName::Fade,
+        AnimationName::Layers
+    ];
+    pub fn switchable(&self) -> bool { //windows and workspaces are switched off through their preset
+        !matches!(self, AnimationName::Windows | AnimationName::Workspaces)
+    }
+    pub fn preset_off(&self, win: WindowAnimation, work: WorkAnimation) -> bool { //a preset of none switches off the closing and special ones too
+        match self {
+            AnimationName::Windows | AnimationName::WindowsOut => win == WindowAnimation::None,
+            AnimationName::Workspaces | AnimationName::SpecialWorkspace => work == WorkAnimation::None,
+            AnimationName::Border | AnimationName::Fade | AnimationName::Layers => false
+        }
+    }
+}
+impl AnimationCfg {
+    pub fn new(name: AnimationName) -> Self { //the speeds the presets always had, border and fade as slow as hyprland's example
+        AnimationCfg {
+            name,
+            enabled: true,
+            speed: match name {
+                AnimationName::Border => 10.0,
+                AnimationName::Fade => 7.0,
+                _ => 3.0
+            },
+            curve: default_curve(),
+        }
+    }
+}
+impl Animations {
+    pub fn entry(&self, name: AnimationName) -> AnimationCfg {
+        match self.entries.iter().find(|x| x.name == name) {
+            Some(entry) => entry.clone(),
+            None => AnimationCfg::new(name)
+        }
+    }
+    pub fn entry_mut(&mut self, name: AnimationName) -> &mut AnimationCfg {
+        let i = match self.entries.iter().position(|x| x.name == name) {
+            Some(i) => i,
+            None => {
+                self.entries.push(AnimationCfg::new(name));
+                self.entries.len() - 1
+            }
+        };
+        &mut self.entries[i]
+    }
+    pub fn curves(&self) -> Vec<String> { //what an animation can use, default first
+        let mut curves = vec![default_curve()];
+        for i in 0..self.beziers.len() {
+            if self.bezier_usable(i) {
+                curves.push(self.beziers[i].name.trim().to_string());
+            }
+        }
+        curves
+    }
+    pub fn bezier_usable(&self, i: usize) -> bool { //named, not hyprland's own default and not a repeat of an earlier one
+        let name = self.beziers[i].name.trim();
+        name.len() > 0 && name != default_curve() && !self.beziers[..i].iter().any(|x| x.name.trim() == name)
+    }
+    pub fn name_taken(&self, i: usize, name: &str) -> bool { //whether curve i can't be called this
+        let name = name.trim();
+        name == default_curve() || (0..self.beziers.len()).any(|j| j != i && self.beziers[j].name.trim() == name)
+    }
+    pub fn curve_users(&self, i: usize) -> Vec<usize> { //the entries animated with curve i
+        let mut users = vec![];
+        if self.bezier_usable(i) {
+            for j in 0..self.entries.len() {
+                if self.entries[j].curve.trim() == self.beziers[i].name.trim() {
+                    users.push(j);
+                }
+            }
+        }
+        users
+    }
+}
+impl WorkspaceKeys {
+    pub const ALL: [WorkspaceKeys; 3] = [
+        WorkspaceKeys::Numbers,
+        WorkspaceKeys::Function,
+        WorkspaceKeys::Numpad
+    ];
+    pub fn keys(&self) -> Vec<String> { //iced KeyCode names, in workspace order
+        match self {
+            WorkspaceKeys::Numbers => ["Key1", "Key2", "Key3", "Key4", "Key5", "Key6", "Key7", "Key8", "Key9", "Key0"].iter().map(|x| x.to_string()).collect(),
+            WorkspaceKeys::Function => (1..=12).map(|x| format!("F{x}")).collect(),
+            WorkspaceKeys::Numpad => ["Numpad1", "Numpad2", "Numpad3", "Numpad4", "Numpad5", "Numpad6", "Numpad7", "Numpad8", "Numpad9", "Numpad0"].iter().map(|x| x.to_string()).collect(),
+        }
+    }
+}
+impl FollowMouse {
+    pub const ALL: [FollowMouse; 4] = [
+        FollowMouse::Never,
+        FollowMouse::Always,
+        FollowMouse::Click,
+        FollowMouse::Separate
+    ];
+}
+impl Transform {
+    pub const ALL: [Transform; 8] = [
+        Transform::Normal,
+        Transform::Rotate90,
+        Transform::Rotate180,
+        Transform::Rotate270,
+        Transform::Flipped,
+        Transform::Flipped90,
+        Transform::Flipped180,
+        Transform::Flipped270
+    ];
+}
+impl MonitorCfg {
+    pub fn mode(&self) -> String { //WxH@R, or just WxH when the refresh rate is left to hyprland
+        if self.refresh > 0.0 {
+            format!("{}x{}@{}", self.width, self.height, self.refresh)
+        } else {
+            format!("{}x{}", self.width, self.height)
+        }
+    }
+}
+impl DirectionKeys {
+    pub const ALL: [DirectionKeys; 3] = [
+        DirectionKeys::Arrows,
+        DirectionKeys::Vim,
+        DirectionKeys::Wasd
+    ];
+    pub fn keys(&self) -> [&'static str; 4] { //iced KeyCode names, in the order of DIRECTIONS
+        match self {
+            DirectionKeys::Arrows => ["Left", "Right", "Up", "Down"],
+            DirectionKeys::Vim => ["H", "L", "K", "J"],
+            DirectionKeys::Wasd => ["A", "D", "W", "S"],
+        }
+    }
+}
+impl RuleAction {
+    pub const ALL: [RuleAction; 8] = [
+        RuleAction::Float,
+        RuleAction::Workspace,
+        RuleAction::Size,
+        RuleAction::Move,
+        RuleAction::Opacity,
+        RuleAction::Pin,
+        RuleAction::Fullscreen,
+        RuleAction::IdleInhibit
+    ];
+    pub fn takes_arg(&self) -> bool {
+        !matches!(self, RuleAction::Float | RuleAction::Pin | RuleAction::Fullscreen)
+    }
+    pub fn arg_hint(&self) -> String { //placeholder text for the argument
+        match self {
+            RuleAction::Workspace => tr("workspace, e.g. 2"),
+            RuleAction::Size => tr("width height, e.g. 800 600"),
+            RuleAction::Move => tr("x y, e.g. 100 100"),
+            RuleAction::Opacity => tr("opacity, e.g. 0.9"),
+            RuleAction::IdleInhibit => tr("when, e.g. focus or fullscreen"),
+            RuleAction::Float | RuleAction::Pin | RuleAction::Fullscreen => String::new(),
+        }
+    }
+}
+impl RuleState {
+    pub const ALL: [RuleState; 3] = [
+        RuleState::Any,
+        RuleState::Yes,
+        RuleState::No
+    ];
+}
+impl LayoutEngine {
+    pub const ALL: [LayoutEngine; 2] = [
+        LayoutEngine::Dwindle,
+        LayoutEngine::Master
+    ];
+}
+impl MasterOrientation {
+    pub const ALL: [MasterOrientation; 5] = [
+        MasterOrientation::Left,
+        MasterOrientation::Right,
+        MasterOrientation::Top,
+        MasterOrientation::Bottom,
+        MasterOrientation::Center
+    ];
+}
+impl Workspaces {
+    pub fn count(&self) -> usize { //what actually gets bound
+        (self.count as usize).clamp(1, self.keys.keys().len())
+    }
+    pub fn monitor(&self, workspace: u8) -> String {
+        match self.monitors.iter().find(|x| x.workspace == workspace) {
+            Some(x) => x.monitor.clone(),
+            None => String::new()
+        }
+    }
+    pub fn set_monitor(&mut self, workspace: u8, monitor: String) { //an empty name lets the workspace open anywhere again
+        self.monitors.retain(|x| x.workspace != workspace);
+        if monitor.trim().len() > 0 {
+            self.monitors.push(WorkspaceMonitor {workspace, monitor});
+            self.monitors.sort_by_key(|x| x.workspace);
+        }
+    }
+}
+impl Default for FileData { //what a fresh install starts with
+    fn default() -> Self {
+        let bind = |header: BindKey, key: &str, dispatcher: Dispatcher, arg: &str| Keybind {
+            header,
+            mods: vec![],
+            key: key.to_string(),
+            dispatcher,
+            arg: arg.to_string(),
+        };
+        FileData {
+            version: CFG_VERSION,
+            profile: String::new(),
+            theme: ThemeName::Light,
+            primary: ShortcutKey::Super,
+            wallpaper: String::new(),
+            secondary: ShortcutKey::Shift,
+            binds: vec![
+                bind(BindKey::BothKey, "E", Dispatcher::Exec, "wlogout"),
+                bind(BindKey::PrimaryKey, "Tab", Dispatcher::Exec, "rofi -show drun"),
+                bind(BindKey::BothKey, "Q", Dispatcher::KillActive, ""),
+                bind(BindKey::BothKey, "Z", Dispatcher::MoveToWorkspace, "special"),
+                bind(BindKey::PrimaryKey, "Z", Dispatcher::ToggleSpecialWorkspace, ""),
+            ],
+            border: Border::default(),
+            win_anim: WindowAnimation::PopIn,
+            work_anim: WorkAnimation::SlideVert,
+            animations: Animations::default(),
+            blur: true,
+            widgets_left: vec![BarWidget::CPU, BarWidget::RAM, BarWidget::Temperature, BarWidget::User],
+            widgets_center: vec![BarWidget::Workspaces, BarWidget::Clock],
+            widgets_right: vec![BarWidget::Audio, BarWidget::Backlight, BarWidget::Tray],
+            autostart: vec![],
+            workspaces: Workspaces::default(),
+            directions: Directions::default(),
+            monitors: vec![],
+            input: Input::default(),
+            layout: Layout::default(),
+            window_rules: vec![],
+        }
+    }
+}
+impl Default for Border {
+    fn default() -> Self {
+        Border {
+            width: 5,
+            radius: 15,
+            gaps: 10,
+        }
+    }
+}
 impl std::fmt::Display for ShortcutKey {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -135,6 +889,9 @@ impl std::fmt::Display for ShortcutKey {
                 ShortcutKey::Alt => tr("Alt Key"),
                 ShortcutKey::Shift => tr("Shift Key"),
                 ShortcutKey::Ctrl => tr("Control Key"),
+                ShortcutKey::Mod3 => tr("Hyper Key (Mod3)"),
+                ShortcutKey::Mod5 => tr("AltGr Key (Mod5)"),
+                ShortcutKey::Caps => tr("Caps Lock"),
             }
         )
     }
@@ -147,7 +904,36 @@ impl std::fmt::Display for BindKey {
             match self {
                 BindKey::PrimaryKey => tr("Primary Key"),
                 BindKey::SecondaryKey => tr("Secondary Key"),
-                BindKey::BothKey => tr("Primary + Secondary")
+                BindKey::BothKey => tr("Primary + Secondary"),
+                BindKey::NoKey => tr("No Shortcut Key")
+            }
+        )
+    }
+}
+impl std::fmt::Display for Dispatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Dispatcher::Exec => tr("Run a Command"),
+                Dispatcher::KillActive => tr("Close the Focused App"),
+                Dispatcher::Exit => tr("Exit Hyprland Immediately"),
+                Dispatcher::ToggleFloating => tr("Toggle Floating"),
+                Dispatcher::Fullscreen => tr("Toggle Fullscreen"),
+                Dispatcher::Pin => tr("Pin to All Workspaces"),
+                Dispatcher::PseudoTile => tr("Toggle Pseudotiling"),
+                Dispatcher::CenterWindow => tr("Center a Floating App"),
+                Dispatcher::Workspace => tr("Go to Workspace"),
+                Dispatcher::MoveToWorkspace => tr("Move App to Workspace"),
+                Dispatcher::MoveToWorkspaceSilent => tr("Send App to Workspace"),
+                Dispatcher::ToggleSpecialWorkspace => tr("Show Minimized Apps"),
+                Dispatcher::MoveFocus => tr("Move Focus"),
+                Dispatcher::MoveWindow => tr("Move App"),
+                Dispatcher::ResizeActive => tr("Resize the Focused App"),
+                Dispatcher::Submap => tr("Enter a Submap"),
+                Dispatcher::ToggleSplit => tr("Flip the Split Direction"),
+                Dispatcher::LayoutMsg => tr("Send a Layout Message")
             }
         )
     }
@@ -179,6 +965,157 @@ impl std::fmt::Display for WorkAnimation {
         )
     }
 }
+impl std::fmt::Display for AnimationName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                AnimationName::Windows => tr("Opening Windows"),
+                AnimationName::WindowsOut => tr("Closing Windows"),
+                AnimationName::Workspaces => tr("Switching Workspaces"),
+                AnimationName::SpecialWorkspace => tr("Showing Minimized Apps"),
+                AnimationName::Border => tr("Border Colors"),
+                AnimationName::Fade => tr("Fading"),
+                AnimationName::Layers => tr("Bars and Menus"),
+            }
+        )
+    }
+}
+impl std::fmt::Display for WorkspaceKeys {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                WorkspaceKeys::Numbers => tr("Number Row"),
+                WorkspaceKeys::Function => tr("Function Keys"),
+                WorkspaceKeys::Numpad => tr("Number Pad"),
+            }
+        )
+    }
+}
+impl std::fmt::Display for FollowMouse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                FollowMouse::Never => tr("Only on Click"),
+                FollowMouse::Always => tr("Follows the Pointer"),
+                FollowMouse::Click => tr("Click, Scrolling Follows the Pointer"),
+                FollowMouse::Separate => tr("Separate From the Pointer"),
+            }
+        )
+    }
+}
+impl std::fmt::Display for Transform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Transform::Normal => tr("Normal"),
+                Transform::Rotate90 => tr("Rotated 90°"),
+                Transform::Rotate180 => tr("Rotated 180°"),
+                Transform::Rotate270 => tr("Rotated 270°"),
+                Transform::Flipped => tr("Flipped"),
+                Transform::Flipped90 => tr("Flipped, Rotated 90°"),
+                Transform::Flipped180 => tr("Flipped, Rotated 180°"),
+                Transform::Flipped270 => tr("Flipped, Rotated 270°"),
+            }
+        )
+    }
+}
+impl std::fmt::Display for DirectionKeys {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                DirectionKeys::Arrows => tr("Arrow Keys"),
+                DirectionKeys::Vim => tr("H J K L (Vim)"),
+                DirectionKeys::Wasd => tr("W A S D"),
+            }
+        )
+    }
+}
+impl std::fmt::Display for BuiltInBind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                BuiltInBind::Focus => tr("moving focus"),
+                BuiltInBind::Move => tr("moving windows"),
+                BuiltInBind::Resize => tr("resizing"),
+                BuiltInBind::ResizeMode => tr("the resize mode"),
+                BuiltInBind::Workspace => tr("switching workspaces"),
+                BuiltInBind::MoveToWorkspace => tr("moving to a workspace"),
+                BuiltInBind::Cycle => tr("next and previous workspace"),
+                BuiltInBind::Layout => tr("the layout"),
+            }
+        )
+    }
+}
+impl std::fmt::Display for RuleAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                RuleAction::Float => tr("Float"),
+                RuleAction::Workspace => tr("Open on Workspace"),
+                RuleAction::Size => tr("Set the Size"),
+                RuleAction::Move => tr("Move To"),
+                RuleAction::Opacity => tr("Set the Opacity"),
+                RuleAction::Pin => tr("Pin to All Workspaces"),
+                RuleAction::Fullscreen => tr("Open Fullscreen"),
+                RuleAction::IdleInhibit => tr("Keep the Screen Awake"),
+            }
+        )
+    }
+}
+impl std::fmt::Display for RuleState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                RuleState::Any => tr("Either"),
+                RuleState::Yes => tr("Yes"),
+                RuleState::No => tr("No"),
+            }
+        )
+    }
+}
+impl std::fmt::Display for LayoutEngine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                LayoutEngine::Dwindle => tr("Dwindle (Split in Two)"),
+                LayoutEngine::Master => tr("Master (One Large Window)"),
+            }
+        )
+    }
+}
+impl std::fmt::Display for MasterOrientation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                MasterOrientation::Left => tr("Left"),
+                MasterOrientation::Right => tr("Right"),
+                MasterOrientation::Top => tr("Top"),
+                MasterOrientation::Bottom => tr("Bottom"),
+                MasterOrientation::Center => tr("Center"),
+            }
+        )
+    }
+}
 impl std::fmt::Display for BarWidget {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -205,259 +1142,1223 @@ impl std::fmt::Display for BarWidget {
     }
 }
 
-pub fn get_home() -> String {
+pub const CFG_VERSION: u32 = MIGRATIONS.len() as u32; //the layout this build writes
+const MIGRATIONS: [fn(&mut Table); 2] = [ //MIGRATIONS[n] upgrades a version n file to version n + 1
+    migrate_v0,
+    migrate_v1,
+];
+
+pub fn get_home() -> Result<String, CfgError> {
     match env::var("XDG_CONFIG_HOME") {
-        Ok(var) => var,
+        Ok(var) => Ok(var),
         Err(..) => match env::var("HOME") {
-            Ok(var) => format!("{var}/.config"),
-            Err(..) => panic!("Failed to find config directory, make sure XDG_CONFIG_HOME or HOME are set")
+            Ok(var) => Ok(format!("{var}/.config")),
+            Err(..) => Err(CfgError::NoConfigDir)
         }
     }
 }
-pub fn get_cfg_data() -> FileData {
-    let home = get_home();
+pub fn write_atomic(path: &str, contents: &str) -> Result<(), CfgError> { //readers see either the old file or the new one, never half of it
+    let target = resolve_link(path);
+    let temp = stage_file(&target, contents)?;
+    fs::rename(&temp, &target).map_err(|e| {
+        let _ = fs::remove_file(&temp);
+        CfgError::Io(path.to_string(), e)
+    })?;
+    sync_dir(&target)
+}
+pub fn resolve_link(path: &str) -> String { //a symlinked config, as dotfile managers leave them, is written through rather than replaced
+    match fs::canonicalize(path) {
+        Ok(target) => target.display().to_string(),
+        Err(..) => path.to_string() //not there yet
+    }
+}
+pub fn stage_file(target: &str, contents: &str) -> Result<String, CfgError> { //writes the temp file next to the target and flushes it to disk, returns its path
+    let temp = format!("{target}.tmp");
+    let written = fs::File::create(&temp).and_then(|mut file| {
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()
+    });
+    if let Err(e) = written {
+        let _ = fs::remove_file(&temp);
+        return Err(CfgError::Io(temp, e));
+    }
+    Ok(temp)
+}
+pub fn sync_dir(target: &str) -> Result<(), CfgError> {
+    let dir = match std::path::Path::new(target).parent() {
+        Some(dir) if dir.as_os_str().len() > 0 => dir,
+        _ => std::path::Path::new(".")
+    };
+    fs::File::open(dir).and_then(|x| x.sync_all()).map_err(|e| CfgError::Io(dir.display().to_string(), e))
+}
+pub fn get_cfg_data() -> Result<(FileData, Vec<CfgWarning>), CfgError> {
+    get_cfg_data_in(&get_home()?)
+}
+pub fn get_cfg_data_in(home: &str) -> Result<(FileData, Vec<CfgWarning>), CfgError> { //get_cfg_data for a config directory other than the user's
+    let (file, path) = read_cfg_file(home)?;
+    let mut table: Table = from_str(&file).map_err(CfgError::Parse)?;
+    let migrated_from = migrate_cfg(&mut table);
+    let (data, mut warnings) = decode_cfg_table(table).map_err(CfgError::Parse)?;
+    if let (Some(old), Some(path)) = (migrated_from, path) { //keep the file as it was, then write it back in the new layout
+        let backup = format!("{path}.v{old}.bak");
+        if !std::path::Path::new(&backup).exists() {
+            fs::write(&backup, &file).map_err(|e| CfgError::Io(backup.clone(), e))?;
+        }
+        write_atomic(&path, &toml::to_string(&data).map_err(CfgError::Serialize)?)?; //a crash halfway must not leave half a cfg.toml next to the backup
+        warnings.push(CfgWarning {
+            key: "version".to_string(),
+            message: format!("{} {old} {} {CFG_VERSION}, {} {backup}", tr("upgraded from"), tr("to"), tr("the old file was kept as")),
+        });
+    }
+    Ok((data, warnings))
+}
+pub fn parse_cfg_data(file: &str) -> Result<(FileData, Vec<CfgWarning>), toml::de::Error> {
+    let mut table: Table = from_str(file)?;
+    migrate_cfg(&mut table);
+    decode_cfg_table(table)
+}
+fn decode_cfg_table(mut table: Table) -> Result<(FileData, Vec<CfgWarning>), toml::de::Error> {
+    let mut warnings = sanitize_cfg(&mut table);
+    let data: FileData = Value::Table(table).try_into()?;
+    if data.version > CFG_VERSION { //read as far as we can, the save path refuses to write it back
+        warnings.push(CfgWarning {
+            key: "version".to_string(),
+            message: format!("{} {}, {} {CFG_VERSION}, {}", tr("written for version"), data.version, tr("this build knows up to"), tr("it won't be saved over")),
+        });
+    }
+    Ok((data, warnings))
+}
+pub fn peek_cfg_data() -> Result<(FileData, Vec<CfgWarning>), CfgError> { //get_cfg_data without writing anything back, for commands that only look
+    let file = match find_cfg_file(&get_home()?)? {
+        Some((file, _)) => file,
+        None => return Ok((FileData::default(), vec![]))
+    };
+    let mut table: Table = from_str(&file).map_err(CfgError::Parse)?;
+    let migrated_from = migrate_cfg(&mut table);
+    let (data, mut warnings) = decode_cfg_table(table).map_err(CfgError::Parse)?;
+    if let Some(old) = migrated_from {
+        warnings.push(CfgWarning {
+            key: "version".to_string(),
+            message: format!("{} {old}, {} {CFG_VERSION}", tr("written for version"), tr("the next apply upgrades it to")),
+        });
+    }
+    Ok((data, warnings))
+}
+fn find_cfg_file(home: &str) -> Result<Option<(String, Option<String>)>, CfgError> { //the user's cfg.toml and its path, falling back to the system one, None without either
     let path = format!("{home}/Oceania/cfg.toml");
-    let backup_path = format!("{home}/Oceania");
-    let placeholder = String::from(r#"
-    theme = "light"
-    primary = "super"
-    secondary = "shift"
-    exit_h = "both"
-    exit_k = "E"
-    launch_h = "pri"
-    launch_k = "Tab"
-    kill_h = "both"
-    kill_k = "Q"
-    mini_h = "both"
-    mini_k = "Z"
-    scratch_h = "pri"
-    scratch_k = "Z"
-    win_anim = "popin"
-    work_anim = "slidev"
-    blur = "y"
-    widgets_left = ["CPU", "RAM", "Temperature", "Current User"]
-    widgets_center = ["Workspaces", "Clock"]
-    widgets_right = ["Audio", "Backlight", "System Tray"]
-    
-    [border]
-    width = 5
-    radius = 15
-    gaps = 10"#);
-    let file = match read_to_string(path.clone()) {
-        Ok(var) => var,
-        Err(..) => match read_to_string("/etc/Oceania/cfg.toml") {
-            Ok(var) => var,
-            Err(..) => {
-                std::process::Command::new("mkdir").arg("-p").arg(backup_path).output().expect("uh oh");
-                fs::write(path, placeholder.clone()).expect("failed to write backup file");
-                placeholder
+    match read_to_string(&path) {
+        Ok(var) => return Ok(Some((var, Some(path)))),
+        Err(e) if e.kind() != ErrorKind::NotFound => return Err(CfgError::Io(path, e)),
+        Err(..) => {}
+    }
+    match read_to_string("/etc/Oceania/cfg.toml") {
+        Ok(var) => Ok(Some((var, None))),
+        Err(..) => Ok(None)
+    }
+}
+pub fn read_cfg_file(home: &str) -> Result<(String, Option<String>), CfgError> { //like find_cfg_file, but writes out the defaults when there is no file at all
+    if let Some(found) = find_cfg_file(home)? {
+        return Ok(found);
+    }
+    let placeholder = toml::to_string(&FileData::default()).map_err(CfgError::Serialize)?;
+    let _ = fs::create_dir_all(format!("{home}/Oceania")); //only a convenience for users who want to edit it by hand, the defaults work either way
+    let _ = fs::write(format!("{home}/Oceania/cfg.toml"), &placeholder);
+    Ok((placeholder, None))
+}
+pub fn profile_dir(home: &str) -> String {
+    format!("{home}/Oceania/profiles")
+}
+pub fn profile_path(home: &str, name: &str) -> String {
+    format!("{home}/Oceania/profiles/{name}.toml")
+}
+pub fn check_profile_name(name: &str) -> Result<(), CfgError> { //names end up in a path, so nothing that could leave the profile directory
+    if name.len() > 0 && name.chars().all(|x| x.is_alphanumeric() || x == '-' || x == '_') {
+        Ok(())
+    } else {
+        Err(CfgError::BadProfileName(name.to_string()))
+    }
+}
+pub fn list_profiles() -> Result<Vec<String>, CfgError> {
+    let dir = profile_dir(&get_home()?);
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(CfgError::Io(dir, e))
+    };
+    let mut profiles = vec![];
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if let Some(name) = name.strip_suffix(".toml") {
+            if check_profile_name(name).is_ok() {
+                profiles.push(name.to_string());
             }
         }
+    }
+    profiles.sort();
+    Ok(profiles)
+}
+pub fn load_profile(name: &str) -> Result<(FileData, Vec<CfgWarning>), CfgError> {
+    check_profile_name(name)?;
+    let path = profile_path(&get_home()?, name);
+    let file = match read_to_string(&path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Err(CfgError::UnknownProfile(name.to_string())),
+        Err(e) => return Err(CfgError::Io(path, e))
     };
-    let decoded: FileData = from_str(&file).unwrap();
-    decoded
+    let (mut data, warnings) = parse_cfg_data(&file).map_err(CfgError::Parse)?;
+    data.profile = name.to_string(); //the file may have been copied in by hand under another name
+    Ok((data, warnings))
 }
-pub fn decode_theme(x: &str, default: SelectedTheme) -> SelectedTheme {
-    match x {
-        "dark" => SelectedTheme::Dark,
-        "light" => SelectedTheme::Light,
-        "custom" => SelectedTheme::Custom,
-        &_ => default
-    }
-}
-pub fn decode_work_anim(x: &str, default: WorkAnimation) -> Option<WorkAnimation> {
-    Some(match x {
-        "none" => WorkAnimation::None,
-        "fade" => WorkAnimation::Fade,
-        "slide" => WorkAnimation::Slide,
-        "slidev" => WorkAnimation::SlideVert,
-        &_ => default
-    })
-}
-pub fn decode_win_anim(x: &str, default: WindowAnimation) -> Option<WindowAnimation> {
-    Some(match x {
-        "none" => WindowAnimation::None,
-        "popin" => WindowAnimation::PopIn,
-        "slide" => WindowAnimation::Slide,
-        &_ => default
-    })
-}
-pub fn decode_blur(x: &str) -> bool{
-    match x {
-        "y" => true,
-        "n" => false,
-        &_ => true
-    }
-}
-pub fn decode_pri(x: &str, default: ShortcutKey) -> Option<ShortcutKey> {
-    Some(match x {
-        "super" => ShortcutKey::Super,
-        "alt" => ShortcutKey::Alt,
-        "control" => ShortcutKey::Ctrl,
-        "shift" => ShortcutKey::Shift,
-        &_ => default
-    })
-}
-pub fn decode_header(x: &str, default: BindKey) -> Option<BindKey> {
-    Some(match x {
-        "pri" => BindKey::PrimaryKey,
-        "sec" => BindKey::SecondaryKey,
-        "both" => BindKey::BothKey,
-        &_ => default
-    })
-}
-pub fn decode_widget(x: &str, default: BarWidget) -> BarWidget {
-    match x {
-        "Audio" => BarWidget::Audio,
-        "Backlight" => BarWidget::Backlight,
-        "Battery" => BarWidget::Battery,
-        "Bluetooth" => BarWidget::Bluetooth,
-        "CPU" => BarWidget::CPU,
-        "Clock" => BarWidget::Clock,
-        "Disk" => BarWidget::Disk,
-        "Keyboard State" => BarWidget::KeyboardState,
-        "Network" => BarWidget::Network,
-        "RAM" => BarWidget::RAM,
-        "Taskbar" => BarWidget::Taskbar,
-        "Temperature" => BarWidget::Temperature,
-        "System Tray" => BarWidget::Tray,
-        "Current User" => BarWidget::User,
-        "Workspaces" => BarWidget::Workspaces,
-        &_ => default,
-    }
-}
-pub fn encode_theme(x: SelectedTheme) -> String {
-    match x {
-        SelectedTheme::Dark => "dark".to_string(),
-        SelectedTheme::Light => "light".to_string(),
-        SelectedTheme::Custom => "custom".to_string()
+pub fn migrate_cfg(table: &mut Table) -> Option<u32> { //upgrades an older layout in place, returns the version it started from
+    let v0_keys = ["exith", "exitk", "launchh", "launchk", "killh", "killk", "minih", "minik", "scratchh", "scratchk", "winanim", "workanim"];
+    let old = match table.get("version").and_then(|x| x.as_integer()) {
+        Some(x) => x.max(0) as u32,
+        None if v0_keys.iter().any(|x| table.contains_key(*x)) => 0,
+        None => 1, //the version field came with the second layout change, so anything else without it is version 1
+    };
+    for version in old..CFG_VERSION {
+        MIGRATIONS[version as usize](table);
+    }
+    if old < CFG_VERSION {
+        table.insert("version".to_string(), Value::Integer(CFG_VERSION as i64));
+        Some(old)
+    } else {
+        None
     }
 }
-pub fn encode_pri(x: Option<ShortcutKey>) -> String {
-    match x.unwrap() {
-        ShortcutKey::Super => "super".to_string(),
-        ShortcutKey::Alt => "alt".to_string(),
-        ShortcutKey::Ctrl => "control".to_string(),
-        ShortcutKey::Shift => "shift".to_string()
+fn migrate_v0(table: &mut Table) { //the first layout had no underscores in its keys
+    let renames = [
+        ("exith", "exit_h"), ("exitk", "exit_k"),
+        ("launchh", "launch_h"), ("launchk", "launch_k"),
+        ("killh", "kill_h"), ("killk", "kill_k"),
+        ("minih", "mini_h"), ("minik", "mini_k"),
+        ("scratchh", "scratch_h"), ("scratchk", "scratch_k"),
+        ("winanim", "win_anim"), ("workanim", "work_anim"),
+    ];
+    for (old, new) in renames {
+        if let Some(value) = table.remove(old) {
+            if !table.contains_key(new) {
+                table.insert(new.to_string(), value);
+            }
+        }
     }
 }
-pub fn encode_header(x: Option<BindKey>) -> String {
-    match x.unwrap() {
-        BindKey::PrimaryKey => "pri".to_string(),
-        BindKey::SecondaryKey => "sec".to_string(),
-        BindKey::BothKey => "both".to_string()
+fn migrate_v1(table: &mut Table) { //the five fixed bindings became a list
+    let fixed = [
+        ("exit", "both", "exec", "wlogout"),
+        ("launch", "pri", "exec", "rofi -show drun"),
+        ("kill", "both", "killactive", ""),
+        ("mini", "both", "movetoworkspace", "special"),
+        ("scratch", "pri", "togglespecialworkspace", ""),
+    ];
+    let mut binds = vec![];
+    for (name, header, dispatcher, arg) in fixed {
+        let old_header = table.remove(&format!("{name}_h"));
+        if let Some(key) = table.remove(&format!("{name}_k")) {
+            let mut bind = Table::new();
+            bind.insert("header".to_string(), old_header.unwrap_or(Value::String(header.to_string())));
+            bind.insert("key".to_string(), key);
+            bind.insert("dispatcher".to_string(), Value::String(dispatcher.to_string()));
+            bind.insert("arg".to_string(), Value::String(arg.to_string()));
+            binds.push(Value::Table(bind));
+        }
+    }
+    if binds.len() > 0 && !table.contains_key("binds") {
+        table.insert("binds".to_string(), Value::Array(binds));
     }
 }
-pub fn encode_work_anim(x: Option<WorkAnimation>) -> String {
-    match x.unwrap() {
-        WorkAnimation::None => "none".to_string(),
-        WorkAnimation::Fade => "fade".to_string(),
-        WorkAnimation::Slide => "slide".to_string(),
-        WorkAnimation::SlideVert => "slidev".to_string(),
+impl std::fmt::Display for CfgError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CfgError::NoConfigDir => write!(f, "{}", tr("Failed to find config directory, make sure XDG_CONFIG_HOME or HOME are set")),
+            CfgError::Parse(e) => write!(f, "{}: {e}", tr("cfg.toml could not be read")),
+            CfgError::Serialize(e) => write!(f, "{}: {e}", tr("the settings could not be written as toml")),
+            CfgError::Io(path, e) => write!(f, "{path}: {e}"),
+            CfgError::MissingTool(name) => write!(f, "{name}: {}", tr("not installed or could not be started")),
+            CfgError::BadProfileName(name) => write!(f, "\"{name}\": {}", tr("profile names may only use letters, numbers, - and _")),
+            CfgError::UnknownProfile(name) => write!(f, "\"{name}\": {}", tr("no such profile")),
+            CfgError::Invalid(path, line, message) => write!(f, "{path}:{line}: {message}, {}", tr("nothing was saved")),
+            CfgError::BadOutput(program, message) => write!(f, "{program}: {}: {message}", tr("unexpected output")),
+            CfgError::NewerVersion(version) => write!(f, "{} {version}, {}", tr("cfg.toml was written by a newer cuttlefish, layout"), tr("nothing was saved so its settings are not lost")),
+        }
     }
 }
-pub fn encode_win_anim(x: Option<WindowAnimation>) -> String {
-    match x.unwrap() {
-        WindowAnimation::None => "none".to_string(),
-        WindowAnimation::PopIn => "popin".to_string(),
-        WindowAnimation::Slide => "slide".to_string()
+impl std::error::Error for CfgError {}
+impl std::fmt::Display for CfgWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.key, self.message)
     }
 }
-pub fn encode_blur(x: bool) -> String {
-    if x {
-        "y".to_string()
-    } else {
-        "n".to_string()
+mod yes_no { //blur is stored as "y" or "n"
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+    pub fn serialize<S: Serializer>(x: &bool, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(if *x {"y"} else {"n"})
+    }
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+        match String::deserialize(deserializer)?.as_str() {
+            "y" => Ok(true),
+            "n" => Ok(false),
+            x => Err(D::Error::custom(format!("unknown value \"{x}\"")))
+        }
+    }
+}
+fn known<T: DeserializeOwned>(value: &Value) -> bool {
+    value.clone().try_into::<T>().is_ok()
+}
+fn sanitize_value<T: serde::Serialize>(table: &mut Table, key: &str, known: fn(&Value) -> bool, fallback: T, warnings: &mut Vec<CfgWarning>) {
+    let fallback = Value::try_from(fallback).unwrap();
+    if let Some(value) = table.get(key) {
+        if !known(value) {
+            warnings.push(CfgWarning {
+                key: key.to_string(),
+                message: format!("{} {value}, {} {fallback}", tr("unknown value"), tr("using")),
+            });
+            table.insert(key.to_string(), fallback);
+        }
+    }
+}
+fn sanitize_list(table: &mut Table, key: &str, known: fn(&Value) -> bool, warnings: &mut Vec<CfgWarning>) { //unknown entries are left out rather than guessed at
+    if let Some(Value::Array(list)) = table.get_mut(key) {
+        let mut i = 0;
+        list.retain(|value| {
+            if !known(value) {
+                warnings.push(CfgWarning {
+                    key: format!("{key}[{i}]"),
+                    message: format!("{} {value}, {}", tr("unknown value"), tr("left out")),
+                });
+            }
+            i += 1;
+            known(value)
+        });
     }
 }
-pub fn encode_widget(x: BarWidget) -> String {
+pub fn sanitize_cfg(table: &mut Table) -> Vec<CfgWarning> { //swaps every value the enums don't know for a default, saying so
+    let mut warnings = vec![];
+    sanitize_value(table, "theme", known::<ThemeName>, ThemeName::Light, &mut warnings);
+    sanitize_value(table, "primary", known::<ShortcutKey>, ShortcutKey::Super, &mut warnings);
+    sanitize_value(table, "secondary", known::<ShortcutKey>, ShortcutKey::Shift, &mut warnings);
+    sanitize_value(table, "win_anim", known::<WindowAnimation>, WindowAnimation::None, &mut warnings);
+    sanitize_value(table, "work_anim", known::<WorkAnimation>, WorkAnimation::None, &mut warnings);
+    sanitize_value(table, "blur", |x| yes_no::deserialize(x.clone()).is_ok(), "y", &mut warnings);
+    sanitize_list(table, "widgets_left", known::<BarWidget>, &mut warnings);
+    sanitize_list(table, "widgets_center", known::<BarWidget>, &mut warnings);
+    sanitize_list(table, "widgets_right", known::<BarWidget>, &mut warnings);
+    if let Some(Value::Array(binds)) = table.get_mut("binds") {
+        let mut kept = vec![];
+        for i in 0..binds.len() {
+            let key = format!("binds[{i}]");
+            let bind = match &mut binds[i] {
+                Value::Table(bind) => bind,
+                _ => {
+                    warnings.push(CfgWarning {key, message: tr("not a binding, left out")});
+                    continue;
+                }
+            };
+            let mut unknown = false;
+            for (field, check) in [("header", known::<BindKey> as fn(&Value) -> bool), ("dispatcher", known::<Dispatcher>)] {
+                if let Some(value) = bind.get(field) {
+                    if !check(value) {
+                        warnings.push(CfgWarning {
+                            key: format!("{key}.{field}"),
+                            message: format!("{} {value}, {}", tr("unknown value"), tr("binding left out")),
+                        });
+                        unknown = true;
+                    }
+                }
+            }
+            if unknown {
+                continue;
+            }
+            let mut mod_warnings = vec![];
+            sanitize_list(bind, "mods", known::<ShortcutKey>, &mut mod_warnings);
+            for mut warning in mod_warnings {
+                warning.key = format!("{key}.{}", warning.key);
+                warnings.push(warning);
+            }
+            if bind.get("key").and_then(|x| x.as_str()) == Some("") {
+                warnings.push(CfgWarning {key: format!("{key}.key"), message: tr("no key set")});
+            }
+            kept.push(binds[i].clone());
+        }
+        *binds = kept;
+    }
+    if let Some(Value::Table(workspaces)) = table.get_mut("workspaces") {
+        let mut workspace_warnings = vec![];
+        sanitize_value(workspaces, "keys", known::<WorkspaceKeys>, WorkspaceKeys::Numbers, &mut workspace_warnings);
+        for mut warning in workspace_warnings {
+            warning.key = format!("workspaces.{}", warning.key);
+            warnings.push(warning);
+        }
+    }
+    if let Some(Value::Array(monitors)) = table.get_mut("monitors") {
+        for i in 0..monitors.len() {
+            if let Value::Table(monitor) = &mut monitors[i] {
+                let mut monitor_warnings = vec![];
+                sanitize_value(monitor, "transform", known::<Transform>, Transform::Normal, &mut monitor_warnings);
+                for mut warning in monitor_warnings {
+                    warning.key = format!("monitors[{i}].{}", warning.key);
+                    warnings.push(warning);
+                }
+            }
+        }
+    }
+    if let Some(Value::Array(rules)) = table.get_mut("window_rules") {
+        for i in 0..rules.len() {
+            if let Value::Table(rule) = &mut rules[i] {
+                let mut rule_warnings = vec![];
+                sanitize_value(rule, "action", known::<RuleAction>, RuleAction::Float, &mut rule_warnings);
+                sanitize_value(rule, "floating", known::<RuleState>, RuleState::Any, &mut rule_warnings);
+                sanitize_value(rule, "xwayland", known::<RuleState>, RuleState::Any, &mut rule_warnings);
+                for mut warning in rule_warnings {
+                    warning.key = format!("window_rules[{i}].{}", warning.key);
+                    warnings.push(warning);
+                }
+            }
+        }
+    }
+    if let Some(Value::Table(animations)) = table.get_mut("animations") {
+        if let Some(Value::Array(entries)) = animations.get_mut("entries") {
+            for i in 0..entries.len() {
+                if let Value::Table(entry) = &mut entries[i] {
+                    let mut entry_warnings = vec![];
+                    sanitize_value(entry, "name", known::<AnimationName>, AnimationName::Windows, &mut entry_warnings);
+                    for mut warning in entry_warnings {
+                        warning.key = format!("animations.entries[{i}].{}", warning.key);
+                        warnings.push(warning);
+                    }
+                }
+            }
+        }
+    }
+    if let Some(Value::Table(layout)) = table.get_mut("layout") {
+        let mut layout_warnings = vec![];
+        sanitize_value(layout, "engine", known::<LayoutEngine>, LayoutEngine::Dwindle, &mut layout_warnings);
+        sanitize_value(layout, "orientation", known::<MasterOrientation>, MasterOrientation::Left, &mut layout_warnings);
+        for mut warning in layout_warnings {
+            warning.key = format!("layout.{}", warning.key);
+            warnings.push(warning);
+        }
+    }
+    if let Some(Value::Table(input)) = table.get_mut("input") {
+        let mut input_warnings = vec![];
+        sanitize_value(input, "follow_mouse", known::<FollowMouse>, FollowMouse::Always, &mut input_warnings);
+        for mut warning in input_warnings {
+            warning.key = format!("input.{}", warning.key);
+            warnings.push(warning);
+        }
+    }
+    if let Some(Value::Table(directions)) = table.get_mut("directions") {
+        let mut direction_warnings = vec![];
+        sanitize_value(directions, "keys", known::<DirectionKeys>, DirectionKeys::Arrows, &mut direction_warnings);
+        sanitize_value(directions, "resize_mod", known::<ShortcutKey>, ShortcutKey::Ctrl, &mut direction_warnings);
+        for mut warning in direction_warnings {
+            warning.key = format!("directions.{}", warning.key);
+            warnings.push(warning);
+        }
+    }
+    warnings
+}
+pub fn encode<T: serde::Serialize>(x: &T) -> String { //the string a value is stored as in cfg.toml
+    match Value::try_from(x) {
+        Ok(Value::String(x)) => x,
+        Ok(x) => x.to_string(),
+        Err(..) => String::new()
+    }
+}
+pub fn decode<T: DeserializeOwned>(x: &str) -> Option<T> {
+    Value::String(x.to_string()).try_into().ok()
+}
+pub fn decode_theme(x: ThemeName) -> SelectedTheme {
     match x {
-        BarWidget::Audio => "Audio",
-        BarWidget::Backlight => "Backlight",
-        BarWidget::Battery => "Battery",
-        BarWidget::Bluetooth => "Bluetooth",
-        BarWidget::CPU => "CPU",
-        BarWidget::Clock => "Clock",
-        BarWidget::Disk => "Disk",
-        BarWidget::KeyboardState => "Keyboard State",
-        BarWidget::Network => "Network",
-        BarWidget::RAM => "RAM",
-        BarWidget::Taskbar => "Taskbar",
-        BarWidget::Temperature => "Temperature",
-        BarWidget::Tray => "System Tray",
-        BarWidget::User => "Current User",
-        BarWidget::Workspaces => "Workspaces"
-    }.to_string()
+        ThemeName::Dark => SelectedTheme::Dark,
+        ThemeName::Light => SelectedTheme::Light,
+        ThemeName::Custom => SelectedTheme::Custom
+    }
+}
+pub fn encode_theme(x: SelectedTheme) -> ThemeName {
+    match x {
+        SelectedTheme::Dark => ThemeName::Dark,
+        SelectedTheme::Light => ThemeName::Light,
+        SelectedTheme::Custom => ThemeName::Custom
+    }
 }
 pub fn rip_shortcut(opt: Option<ShortcutKey>) -> String {
     match opt.unwrap() {
         ShortcutKey::Alt => "ALT".to_string(),
         ShortcutKey::Ctrl => "CONTROL".to_string(),
         ShortcutKey::Shift => "SHIFT".to_string(),
-        ShortcutKey::Super => "SUPER".to_string()
+        ShortcutKey::Super => "SUPER".to_string(),
+        ShortcutKey::Mod3 => "MOD3".to_string(),
+        ShortcutKey::Mod5 => "MOD5".to_string(),
+        ShortcutKey::Caps => "CAPS".to_string()
+    }
+}
+pub fn rip_bind(opt: Option<BindKey>) -> String { //the variables render_hyprland defines, so changing a shortcut key only changes one line
+    match opt.unwrap() {
+        BindKey::PrimaryKey => "$mainMod".to_string(),
+        BindKey::SecondaryKey => "$secMod".to_string(),
+        BindKey::BothKey => "$mainMod $secMod".to_string(),
+        BindKey::NoKey => String::new()
+    }
+}
+pub fn rip_dispatcher(x: Dispatcher) -> String {
+    match x {
+        Dispatcher::Exec => "exec",
+        Dispatcher::KillActive => "killactive",
+        Dispatcher::Exit => "exit",
+        Dispatcher::ToggleFloating => "togglefloating",
+        Dispatcher::Fullscreen => "fullscreen",
+        Dispatcher::Pin => "pin",
+        Dispatcher::PseudoTile => "pseudo",
+        Dispatcher::CenterWindow => "centerwindow",
+        Dispatcher::Workspace => "workspace",
+        Dispatcher::MoveToWorkspace => "movetoworkspace",
+        Dispatcher::MoveToWorkspaceSilent => "movetoworkspacesilent",
+        Dispatcher::ToggleSpecialWorkspace => "togglespecialworkspace",
+        Dispatcher::MoveFocus => "movefocus",
+        Dispatcher::MoveWindow => "movewindow",
+        Dispatcher::ResizeActive => "resizeactive",
+        Dispatcher::Submap => "submap",
+        Dispatcher::ToggleSplit => "togglesplit",
+        Dispatcher::LayoutMsg => "layoutmsg"
+    }.to_string()
+}
+const KEY_NAMES: [(&str, &str); 41] = [ //iced KeyCode name, xkb keysym, for every key where the two differ
+    ("Key0", "0"),
+    ("Key1", "1"),
+    ("Key2", "2"),
+    ("Key3", "3"),
+    ("Key4", "4"),
+    ("Key5", "5"),
+    ("Key6", "6"),
+    ("Key7", "7"),
+    ("Key8", "8"),
+    ("Key9", "9"),
+    ("Left", "left"),
+    ("Right", "right"),
+    ("Up", "up"),
+    ("Down", "down"),
+    ("Space", "space"),
+    ("Enter", "Return"),
+    ("Backspace", "BackSpace"),
+    ("Minus", "minus"),
+    ("Equals", "equal"),
+    ("Grave", "grave"),
+    ("Slash", "slash"),
+    ("Backslash", "backslash"),
+    ("Semicolon", "semicolon"),
+    ("Apostrophe", "apostrophe"),
+    ("Comma", "comma"),
+    ("Period", "period"),
+    ("LBracket", "bracketleft"),
+    ("RBracket", "bracketright"),
+    ("PageUp", "Page_Up"),
+    ("PageDown", "Page_Down"),
+    ("Snapshot", "Print"),
+    ("Numpad0", "KP_0"),
+    ("Numpad1", "KP_1"),
+    ("Numpad2", "KP_2"),
+    ("Numpad3", "KP_3"),
+    ("Numpad4", "KP_4"),
+    ("Numpad5", "KP_5"),
+    ("Numpad6", "KP_6"),
+    ("Numpad7", "KP_7"),
+    ("Numpad8", "KP_8"),
+    ("Numpad9", "KP_9"),
+];
+pub fn rip_key(key: &str) -> String { //captured keys are iced KeyCode names, hyprland wants xkb keysyms
+    match KEY_NAMES.iter().find(|x| x.0 == key) {
+        Some((_, keysym)) => keysym.to_string(),
+        None => key.to_string()
     }
 }
-pub fn rip_bind(opt: Option<BindKey>, pri: Option<ShortcutKey>, sec: Option<ShortcutKey>) -> String {
-    let pri_str = match pri.unwrap() {
-        ShortcutKey::Super => "SUPER",
-        ShortcutKey::Alt => "ALT",
-        ShortcutKey::Ctrl => "CONTROL",
-        ShortcutKey::Shift => "SHIFT"
+pub fn unrip_key(keysym: &str) -> String { //the reverse of rip_key, for reading binds someone else wrote
+    if let Some((name, _)) = KEY_NAMES.iter().find(|x| x.1.eq_ignore_ascii_case(keysym)) {
+        return name.to_string();
+    }
+    if keysym.len() == 1 && keysym.chars().all(|x| x.is_ascii_alphabetic()) { //hyprland takes q and Q alike, iced calls it Q
+        return keysym.to_uppercase();
+    }
+    keysym.to_string()
+}
+pub fn rip_keybind(bind: &Keybind, pri: Option<ShortcutKey>, sec: Option<ShortcutKey>) -> String {
+    let mut header = rip_bind(Some(bind.header));
+    let mut taken = match bind.header { //extra modifiers the header already covers are left out
+        BindKey::PrimaryKey => vec![pri.unwrap()],
+        BindKey::SecondaryKey => vec![sec.unwrap()],
+        BindKey::BothKey => vec![pri.unwrap(), sec.unwrap()],
+        BindKey::NoKey => vec![]
     };
-    let sec_str = match sec.unwrap() {
-        ShortcutKey::Super => "SUPER",
-        ShortcutKey::Alt => "ALT",
-        ShortcutKey::Ctrl => "CONTROL",
-        ShortcutKey::Shift => "SHIFT"
+    for i in 0..bind.mods.len() {
+        if taken.contains(&bind.mods[i]) {
+            continue;
+        }
+        taken.push(bind.mods[i]);
+        let modifier = rip_shortcut(Some(bind.mods[i]));
+        if header.len() == 0 {
+            header = modifier;
+        } else {
+            header = format!("{header} {modifier}");
+        }
+    }
+    let key = rip_key(&bind.key);
+    let dispatcher = rip_dispatcher(bind.dispatcher);
+    let arg = bind.arg.trim();
+    if arg.len() == 0 {
+        format!("bind={header},{key},{dispatcher}")
+    } else {
+        format!("bind={header},{key},{dispatcher},{arg}")
+    }
+}
+pub fn rip_keybinds(binds: &Vec<Keybind>, pri: Option<ShortcutKey>, sec: Option<ShortcutKey>) -> String {
+    let mut output = String::new();
+    for bind in binds {
+        output = format!("{output} {}\n", rip_keybind(bind, pri, sec));
+    }
+    output
+}
+pub const CYCLE_KEYS: [&str; 4] = ["Period", "Comma", "mouse_down", "mouse_up"]; //next then previous, on the keyboard and the wheel
+pub fn rip_workspaces(workspaces: &Workspaces) -> String { //bound to the primary key, moving a window adds the secondary
+    let keys = workspaces.keys.keys();
+    let count = workspaces.count();
+    let dispatcher = if workspaces.follow {Dispatcher::MoveToWorkspace} else {Dispatcher::MoveToWorkspaceSilent};
+    let mut output = String::new();
+    for i in 0..count {
+        output = format!("{output} bind = $mainMod,{}, workspace, {} \n", rip_key(&keys[i]), i + 1);
+    }
+    for i in 0..count {
+        output = format!("{output} bind = $mainMod $secMod,{},{},{} \n", rip_key(&keys[i]), rip_dispatcher(dispatcher), i + 1);
+    }
+    if workspaces.cycle {
+        for i in 0..CYCLE_KEYS.len() {
+            let direction = if i % 2 == 0 {"e+1"} else {"e-1"};
+            output = format!("{output} bind = $mainMod, {}, workspace, {direction}\n", rip_key(CYCLE_KEYS[i]));
+        }
+    }
+    for monitor in &workspaces.monitors {
+        if (monitor.workspace as usize) <= count {
+            output = format!("{output} workspace = {}, monitor:{}\n", monitor.workspace, monitor.monitor.trim());
+        }
+    }
+    output
+}
+pub fn rip_input(input: &Input) -> String { //the whole input block
+    let follow_mouse = FollowMouse::ALL.iter().position(|x| *x == input.follow_mouse).unwrap();
+    format!(" input {{\n \
+    kb_layout = {}\n \
+    kb_variant = {}\n \
+    repeat_rate = {}\n \
+    repeat_delay = {}\n \
+    sensitivity = {}\n \
+    natural_scroll = {}\n \
+    follow_mouse = {follow_mouse}\n \
+    touchpad {{\n \
+    natural_scroll = {}\n \
+    tap-to-click = {}\n \
+    }}\n \
+    }}\n", input.kb_layout.trim(), input.kb_variant.trim(), input.repeat_rate, input.repeat_delay, input.sensitivity, input.natural_scroll, input.touchpad_natural_scroll, input.tap_to_click)
+}
+pub fn rip_monitors(monitors: &Vec<MonitorCfg>) -> String {
+    let mut output = String::new();
+    for monitor in monitors {
+        output = format!("{output} monitor = {}, {}, {}x{}, {}", monitor.name, monitor.mode(), monitor.x, monitor.y, monitor.scale);
+        if monitor.transform != Transform::Normal {
+            output = format!("{output}, transform, {}", Transform::ALL.iter().position(|x| *x == monitor.transform).unwrap());
+        }
+        output = format!("{output}\n");
+    }
+    output
+}
+pub const DIRECTIONS: [&str; 4] = ["l", "r", "u", "d"];
+pub const RESIZE_STEPS: [&str; 4] = ["-20 0", "20 0", "0 -20", "0 20"]; //pixels per press, in the order of DIRECTIONS
+pub const RESIZE_SUBMAP: &str = "resize";
+pub fn rip_directions(directions: &Directions, pri: Option<ShortcutKey>) -> String { //focus on the primary key, moving adds the secondary
+    let keys = directions.keys.keys();
+    let mut output = String::new();
+    for i in 0..keys.len() {
+        output = format!("{output} bind = $mainMod, {}, movefocus, {}\n", rip_key(keys[i]), DIRECTIONS[i]);
+    }
+    for i in 0..keys.len() {
+        output = format!("{output} bind = $mainMod $secMod, {}, movewindow, {}\n", rip_key(keys[i]), DIRECTIONS[i]);
+    }
+    if directions.resize {
+        let mods = if pri == Some(directions.resize_mod) { //the primary key changed to the resize key since, don't name it twice
+            "$mainMod".to_string()
+        } else {
+            format!("$mainMod {}", rip_shortcut(Some(directions.resize_mod)))
+        };
+        for i in 0..keys.len() {
+            output = format!("{output} binde = {mods}, {}, resizeactive, {}\n", rip_key(keys[i]), RESIZE_STEPS[i]);
+        }
+    }
+    if directions.submap {
+        output = format!("{output} bind = $mainMod, {}, submap, {RESIZE_SUBMAP}\n submap = {RESIZE_SUBMAP}\n", rip_key(&directions.submap_key));
+        for i in 0..keys.len() {
+            output = format!("{output} binde = , {}, resizeactive, {}\n", rip_key(keys[i]), RESIZE_STEPS[i]);
+        }
+        output = format!("{output} bind = , Escape, submap, reset\n submap = reset\n");
+    }
+    output
+}
+pub const PSEUDO_KEY: &str = "P";
+pub const SPLIT_KEY: &str = "T";
+pub const SWAP_MASTER_KEY: &str = "M";
+pub fn rip_layout(layout: &Layout) -> String { //only the section of the engine in use
+    match layout.engine {
+        LayoutEngine::Dwindle => format!(" dwindle {{\n \
+        pseudotile = {}\n \
+        preserve_split = {}\n \
+        }}\n", layout.pseudotile, layout.preserve_split),
+        LayoutEngine::Master => format!(" master {{\n \
+        orientation = {}\n \
+        new_is_master = {}\n \
+        }}\n", encode(&layout.orientation), layout.new_is_master)
+    }
+}
+pub fn rip_layout_binds(layout: &Layout) -> String {
+    let mut output = String::new();
+    match layout.engine {
+        LayoutEngine::Dwindle => {
+            if layout.pseudotile {
+                output = format!("{output} bind = $mainMod, {PSEUDO_KEY}, pseudo\n");
+            }
+            output = format!("{output} bind = $mainMod, {SPLIT_KEY}, togglesplit\n");
+        }
+        LayoutEngine::Master => output = format!("{output} bind = $mainMod, {SWAP_MASTER_KEY}, layoutmsg, swapwithmaster\n")
+    }
+    output
+}
+pub fn rip_window_rule(rule: &WindowRule) -> Option<String> { //None while the rule is unfinished, hyprland rejects a rule without a condition
+    let mut conditions = vec![];
+    if rule.class.trim().len() > 0 {
+        conditions.push(format!("class:{}", rule.class.trim()));
+    }
+    if rule.title.trim().len() > 0 {
+        conditions.push(format!("title:{}", rule.title.trim()));
+    }
+    for (name, state) in [("floating", rule.floating), ("xwayland", rule.xwayland)] {
+        match state {
+            RuleState::Any => {}
+            RuleState::Yes => conditions.push(format!("{name}:1")),
+            RuleState::No => conditions.push(format!("{name}:0")),
+        }
+    }
+    if conditions.len() == 0 || rule.action.takes_arg() && rule.arg.trim().len() == 0 {
+        return None;
+    }
+    let action = if rule.action.takes_arg() {
+        format!("{} {}", encode(&rule.action), rule.arg.trim())
+    } else {
+        encode(&rule.action)
     };
-    match opt.unwrap() {
-        BindKey::PrimaryKey => pri_str.to_string(),
-        BindKey::SecondaryKey => sec_str.to_string(),
-        BindKey::BothKey => format!("{pri_str}_{sec_str}")
+    Some(format!("windowrulev2 = {action}, {}", conditions.join(", ")))
+}
+pub fn rip_window_rules(rules: &Vec<WindowRule>) -> String {
+    let mut output = String::new();
+    for rule in rules {
+        if let Some(line) = rip_window_rule(rule) {
+            output = format!("{output} {line}\n");
+        }
     }
+    output
 }
-pub fn rip_win_anim(opt: Option<WindowAnimation>) -> String {
-    match opt.unwrap() {
-        WindowAnimation::None => "0,1,default".to_string(),
-        WindowAnimation::PopIn => "1,3,default,popin".to_string(),
-        WindowAnimation::Slide => "1,3,default,slide".to_string()
+pub fn rip_autostart(entries: &Vec<AutostartEntry>) -> String { //disabled entries stay in cfg.toml but are left out of hyprland.conf
+    let mut output = String::new();
+    for entry in entries {
+        if entry.enabled {
+            output = format!("{output} exec-once={}\n", entry.command);
+        }
     }
+    output
 }
-pub fn rip_work_anim(opt: Option<WorkAnimation>) -> String {
-    match opt.unwrap() {
-        WorkAnimation::None => "0,3,default".to_string(),
-        WorkAnimation::Fade => "1,3,default,fade".to_string(),
-        WorkAnimation::Slide => "1,3,default,slide".to_string(),
-        WorkAnimation::SlideVert => "1,3,default,slidevert".to_string()
+pub fn rip_win_anim(win: WindowAnimation, popin: u32) -> Option<String> { //the style for windows, None switches them off
+    match win {
+        WindowAnimation::None => None,
+        WindowAnimation::PopIn => Some(format!("popin {popin}%")),
+        WindowAnimation::Slide => Some("slide".to_string())
+    }
+}
+pub fn rip_work_anim(work: WorkAnimation) -> Option<String> {
+    match work {
+        WorkAnimation::None => None,
+        WorkAnimation::Fade => Some("fade".to_string()),
+        WorkAnimation::Slide => Some("slide".to_string()),
+        WorkAnimation::SlideVert => Some("slidevert".to_string())
+    }
+}
+pub fn rip_animation(animations: &Animations, name: AnimationName, win: WindowAnimation, work: WorkAnimation) -> String { //name, on or off, speed, curve and the style if there is one
+    let entry = animations.entry(name);
+    let style = match name {
+        AnimationName::Windows | AnimationName::WindowsOut => rip_win_anim(win, animations.popin),
+        AnimationName::Workspaces | AnimationName::SpecialWorkspace => rip_work_anim(work),
+        AnimationName::Border | AnimationName::Fade | AnimationName::Layers => Some(String::new())
+    };
+    let curve = if animations.curves().contains(&entry.curve.trim().to_string()) {entry.curve.trim()} else {"default"}; //a curve that was deleted since
+    let enabled = entry.enabled || !name.switchable();
+    match style {
+        Some(style) if enabled && style.len() > 0 => format!("{},1,{},{curve},{style}", encode(&name), entry.speed),
+        Some(..) if enabled => format!("{},1,{},{curve}", encode(&name), entry.speed),
+        _ => format!("{},0,{},{curve}", encode(&name), entry.speed)
     }
 }
+pub fn rip_animations(animations: &Animations, win: WindowAnimation, work: WorkAnimation) -> String { //the curves first, hyprland needs them defined before they are used
+    let mut output = String::new();
+    for i in 0..animations.beziers.len() {
+        let bezier = &animations.beziers[i];
+        if animations.bezier_usable(i) { //a repeated name would override the first, and default hyprland's own
+            output = format!("{output} bezier = {}, {}, {}, {}, {}\n", bezier.name.trim(), bezier.x0, bezier.y0, bezier.x1, bezier.y1);
+        }
+    }
+    for name in AnimationName::ALL {
+        output = format!("{output} animation = {}\n", rip_animation(animations, name, win, work));
+    }
+    output
+}
+pub fn rip_widget(widget: BarWidget) -> String {
+    match widget {
+        BarWidget::Audio => { "pulseaudio" }
+        BarWidget::Backlight => { "backlight" }
+        BarWidget::Battery => { "battery" }
+        BarWidget::Bluetooth => { "bluetooth" }
+        BarWidget::Clock => { "clock" }
+        BarWidget::CPU => { "cpu" }
+        BarWidget::Disk => { "disk" }
+        BarWidget::KeyboardState => { "keyboard-state" }
+        BarWidget::RAM => { "memory" }
+        BarWidget::Network => { "network" }
+        BarWidget::Temperature => { "temperature" }
+        BarWidget::Tray => { "tray" }
+        BarWidget::Taskbar => { "wlr/taskbar" }
+        BarWidget::Workspaces => { "hyprland/workspaces" }
+        BarWidget::User => { "custom/user" }
+    }.to_string()
+}
 pub fn rip_widget_vec(input_widgets: Vec<BarWidget> ) -> String {
     let mut output = format!("[");
     let quote = r#"""#;
     for i in 0..input_widgets.len() {
-        let mut widget_str = match input_widgets[i] {
-            BarWidget::Audio => { "pulseaudio" }
-            BarWidget::Backlight => { "backlight" }
-            BarWidget::Battery => { "battery" }
-            BarWidget::Bluetooth => { "bluetooth" }
-            BarWidget::Clock => { "clock" }
-            BarWidget::CPU => { "cpu" }
-            BarWidget::Disk => { "disk" }
-            BarWidget::KeyboardState => { "keyboard-state" }
-            BarWidget::RAM => { "memory" }
-            BarWidget::Network => { "network" }
-            BarWidget::Temperature => { "temperature" }
-            BarWidget::Tray => { "tray" }
-            BarWidget::Taskbar => { "wlr/taskbar" }
-            BarWidget::Workspaces => { "hyprland/workspaces" }
-            BarWidget::User => { "custom/user" }
-        }.to_string();
-        widget_str = format!("{quote}{widget_str}{quote}");
+        let widget_str = format!("{quote}{}{quote}", rip_widget(input_widgets[i]));
         if i == input_widgets.len() - 1 {
-            output = format!("{output}{widget_str}]");
+            output = format!("{output}{widget_str}");
         } else {
             output = format!("{output}{widget_str}, ");
         }
     }
-    output
-}
\ No newline at end of file
+    format!("{output}]")
+}
+pub fn rip_widget_cfg(widget: BarWidget) -> String { //the per-module settings block waybar expects for each widget
+    let body = match widget {
+        BarWidget::Audio => r#"
+        "format": "VOL {volume}%",
+        "format-muted": "VOL muted",
+        "on-click": "pavucontrol""#,
+        BarWidget::Backlight => r#"
+        "format": "BRT {percent}%""#,
+        BarWidget::Battery => r#"
+        "states": { "warning": 30, "critical": 15 },
+        "format": "BAT {capacity}%",
+        "format-charging": "CHG {capacity}%""#,
+        BarWidget::Bluetooth => r#"
+        "format": "BT {status}",
+        "format-connected": "BT {device_alias}",
+        "on-click": "blueman-manager""#,
+        BarWidget::Clock => r#"
+        "format": "{:%H:%M}",
+        "tooltip-format": "{:%A, %d %B %Y}""#,
+        BarWidget::CPU => r#"
+        "format": "CPU {usage}%",
+        "interval": 5"#,
+        BarWidget::Disk => r#"
+        "format": "DISK {percentage_used}%",
+        "path": "/""#,
+        BarWidget::KeyboardState => r#"
+        "capslock": true,
+        "numlock": true,
+        "format": "{name} {icon}",
+        "format-icons": { "locked": "on", "unlocked": "off" }"#,
+        BarWidget::RAM => r#"
+        "format": "RAM {percentage}%",
+        "interval": 5"#,
+        BarWidget::Network => r#"
+        "format-wifi": "{essid} {signalStrength}%",
+        "format-ethernet": "{ipaddr}",
+        "format-disconnected": "Offline""#,
+        BarWidget::Temperature => r#"
+        "format": "{temperatureC}°C",
+        "critical-threshold": 80"#,
+        BarWidget::Tray => r#"
+        "spacing": 10"#,
+        BarWidget::Taskbar => r#"
+        "on-click": "activate",
+        "on-click-middle": "close""#,
+        BarWidget::Workspaces => r#"
+        "format": "{name}",
+        "on-click": "activate""#,
+        BarWidget::User => r#"
+        "exec": "whoami",
+        "interval": "once",
+        "format": "{}""#,
+    };
+    format!("    \"{}\": {{{body}\n    }}", rip_widget(widget))
+}
+pub fn rip_combo(bind: &Keybind, pri: Option<ShortcutKey>, sec: Option<ShortcutKey>) -> (Vec<ShortcutKey>, String) { //the exact keys a binding needs, for comparing bindings
+    let mut mods = match bind.header {
+        BindKey::PrimaryKey => vec![pri.unwrap()],
+        BindKey::SecondaryKey => vec![sec.unwrap()],
+        BindKey::BothKey => vec![pri.unwrap(), sec.unwrap()],
+        BindKey::NoKey => vec![]
+    };
+    for i in 0..bind.mods.len() {
+        mods.push(bind.mods[i]);
+    }
+    mods.sort_by_key(|x| ShortcutKey::ALL.iter().position(|y| y == x));
+    mods.dedup();
+    (mods, rip_key(&bind.key).to_lowercase())
+}
+pub fn built_in_combos(pri: Option<ShortcutKey>, sec: Option<ShortcutKey>, workspaces: &Workspaces, directions: &Directions, layout: &Layout) -> Vec<(BuiltInBind, (Vec<ShortcutKey>, String))> { //the direction, workspace and layout bindings mk_config always writes
+    let mut keys = vec![]; //what it is for, key, header and extra modifiers
+    for key in directions.keys.keys() {
+        keys.push((BuiltInBind::Focus, key.to_string(), BindKey::PrimaryKey, vec![]));
+        keys.push((BuiltInBind::Move, key.to_string(), BindKey::BothKey, vec![]));
+        if directions.resize {
+            keys.push((BuiltInBind::Resize, key.to_string(), BindKey::PrimaryKey, vec![directions.resize_mod]));
+        }
+    }
+    if directions.submap {
+        keys.push((BuiltInBind::ResizeMode, directions.submap_key.clone(), BindKey::PrimaryKey, vec![]));
+    }
+    for key in &workspaces.keys.keys()[..workspaces.count()] {
+        keys.push((BuiltInBind::Workspace, key.clone(), BindKey::PrimaryKey, vec![]));
+        keys.push((BuiltInBind::MoveToWorkspace, key.clone(), BindKey::BothKey, vec![]));
+    }
+    if workspaces.cycle {
+        for key in CYCLE_KEYS {
+            keys.push((BuiltInBind::Cycle, key.to_string(), BindKey::PrimaryKey, vec![]));
+        }
+    }
+    match layout.engine {
+        LayoutEngine::Dwindle => {
+            if layout.pseudotile {
+                keys.push((BuiltInBind::Layout, PSEUDO_KEY.to_string(), BindKey::PrimaryKey, vec![]));
+            }
+            keys.push((BuiltInBind::Layout, SPLIT_KEY.to_string(), BindKey::PrimaryKey, vec![]));
+        }
+        LayoutEngine::Master => keys.push((BuiltInBind::Layout, SWAP_MASTER_KEY.to_string(), BindKey::PrimaryKey, vec![]))
+    }
+    let mut combos = vec![];
+    for (what, key, header, mods) in keys {
+        let bind = Keybind {
+            header,
+            mods,
+            key,
+            dispatcher: Dispatcher::Exec,
+            arg: String::new(),
+        };
+        combos.push((what, rip_combo(&bind, pri, sec)));
+    }
+    combos
+}
+pub fn inferred_combos(pri: Option<ShortcutKey>, sec: Option<ShortcutKey>, workspaces: &Workspaces, directions: &Directions, layout: &Layout) -> Vec<(Vec<ShortcutKey>, String)> {
+    built_in_combos(pri, sec, workspaces, directions, layout).into_iter().map(|x| x.1).collect()
+}
+pub fn built_in_clashes(pri: Option<ShortcutKey>, sec: Option<ShortcutKey>, workspaces: &Workspaces, directions: &Directions, layout: &Layout) -> Vec<(BuiltInBind, BuiltInBind)> { //e.g. resizing on the primary key alone, the same keys that move focus
+    let combos = built_in_combos(pri, sec, workspaces, directions, layout);
+    let mut clashes = vec![];
+    for i in 0..combos.len() {
+        for j in i + 1..combos.len() {
+            let pair = (combos[i].0, combos[j].0);
+            if combos[i].1 == combos[j].1 && !clashes.contains(&pair) {
+                clashes.push(pair);
+            }
+        }
+    }
+    clashes
+}
+pub fn suggest_key(binds: &Vec<Keybind>, index: usize, pri: Option<ShortcutKey>, sec: Option<ShortcutKey>, workspaces: &Workspaces, directions: &Directions, layout: &Layout) -> Option<String> { //the first letter or number that is still free with the same modifiers
+    let inferred = inferred_combos(pri, sec, workspaces, directions, layout);
+    let mut taken = vec![];
+    for i in 0..binds.len() {
+        if i != index {
+            taken.push(rip_combo(&binds[i], pri, sec));
+        }
+    }
+    let mut candidates = vec![];
+    for letter in 'A'..='Z' {
+        candidates.push(letter.to_string());
+    }
+    for number in 0..10 {
+        candidates.push(format!("Key{number}"));
+    }
+    for candidate in candidates {
+        let mut bind = binds[index].clone();
+        bind.key = candidate.clone();
+        let combo = rip_combo(&bind, pri, sec);
+        if !taken.contains(&combo) && !inferred.contains(&combo) {
+            return Some(candidate);
+        }
+    }
+    None
+}
+pub fn find_conflicts(binds: &Vec<Keybind>, pri: Option<ShortcutKey>, sec: Option<ShortcutKey>, workspaces: &Workspaces, directions: &Directions, layout: &Layout) -> Vec<BindConflict> {
+    let inferred = built_in_combos(pri, sec, workspaces, directions, layout);
+    let mut combos = vec![];
+    for i in 0..binds.len() {
+        combos.push(rip_combo(&binds[i], pri, sec));
+    }
+    let mut conflicts = vec![];
+    for (first, second) in built_in_clashes(pri, sec, workspaces, directions, layout) {
+        conflicts.push(BindConflict {
+            index: None,
+            other: None,
+            built_in: vec![first, second],
+            suggestion: None,
+        });
+    }
+    for i in 0..binds.len() {
+        if binds[i].key.len() == 0 { //not bound to anything yet
+            continue;
+        }
+        let mut other = None;
+        let mut built_in = vec![];
+        for j in 0..binds.len() {
+            if i != j && combos[i] == combos[j] {
+                other = Some(j);
+                break;
+            }
+        }
+        if other.is_none() {
+            if let Some((what, _)) = inferred.iter().find(|x| x.1 == combos[i]) {
+                built_in.push(*what);
+            }
+        }
+        if other.is_some() || built_in.len() > 0 {
+            conflicts.push(BindConflict {
+                index: Some(i),
+                other,
+                built_in,
+                suggestion: suggest_key(binds, i, pri, sec, workspaces, directions, layout),
+            });
+        }
+    }
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trips<T: serde::Serialize + DeserializeOwned + PartialEq + std::fmt::Debug + Copy>(all: &[T]) {
+        let mut seen = vec![];
+        for x in all {
+            let encoded = encode(x);
+            assert_eq!(decode::<T>(&encoded), Some(*x), "{x:?} does not survive a round trip through {encoded:?}");
+            assert!(!seen.contains(&encoded), "{encoded:?} is used by more than one value");
+            seen.push(encoded);
+        }
+    }
+    fn parse(file: &str) -> (FileData, Vec<CfgWarning>) {
+        parse_cfg_data(file).unwrap()
+    }
+    const MINIMAL: &str = r#"
+    theme = "dark"
+    primary = "alt"
+    secondary = "control"
+    wallpaper = "/tmp/wall.png"
+    win_anim = "slide"
+    work_anim = "fade"
+    blur = "n"
+    widgets_left = ["Keyboard State"]
+    widgets_center = []
+    widgets_right = ["System Tray", "Current User"]
+
+    [border]
+    width = 1
+    radius = 2
+    gaps = 3
+    "#;
+
+    #[test]
+    fn defaults_are_clean() {
+        let (data, warnings) = parse(&toml::to_string(&FileData::default()).unwrap());
+        assert_eq!(warnings, vec![]);
+        assert_eq!(data.version, CFG_VERSION);
+        assert_eq!(data.binds, FileData::default().binds);
+    }
+    #[test]
+    fn every_enum_round_trips() {
+        round_trips(&ThemeName::ALL);
+        round_trips(&ShortcutKey::ALL);
+        round_trips(&BindKey::ALL);
+        round_trips(&Dispatcher::ALL);
+        round_trips(&WindowAnimation::ALL);
+        round_trips(&WorkAnimation::ALL);
+        round_trips(&BarWidget::ALL);
+        round_trips(&WorkspaceKeys::ALL);
+        round_trips(&DirectionKeys::ALL);
+        round_trips(&Transform::ALL);
+        round_trips(&FollowMouse::ALL);
+        round_trips(&LayoutEngine::ALL);
+        round_trips(&MasterOrientation::ALL);
+        round_trips(&RuleAction::ALL);
+        round_trips(&RuleState::ALL);
+        round_trips(&AnimationName::ALL);
+    }
+    #[test]
+    fn file_data_round_trips() { //every value of every enum, written out and read back
+        let (mut data, warnings) = parse(MINIMAL);
+        assert_eq!(warnings, vec![]);
+        data.widgets_left = BarWidget::ALL.to_vec();
+        for header in BindKey::ALL {
+            for dispatcher in Dispatcher::ALL {
+                data.binds.push(Keybind {
+                    header,
+                    mods: ShortcutKey::ALL.to_vec(),
+                    key: "Key1".to_string(),
+                    dispatcher,
+                    arg: "1".to_string(),
+                });
+            }
+        }
+        data.autostart.push(AutostartEntry {command: "waybar".to_string(), enabled: false});
+        for transform in Transform::ALL {
+            data.monitors.push(MonitorCfg {
+                name: "DP-1".to_string(),
+                width: 2560,
+                height: 1440,
+                refresh: 143.912,
+                x: -1920,
+                y: 0,
+                scale: 1.25,
+                transform,
+            });
+        }
+        data.input.kb_variant = "intl".to_string();
+        data.input.sensitivity = -0.5;
+        data.input.follow_mouse = FollowMouse::Separate;
+        data.animations.entries[1].enabled = false;
+        data.animations.entries[2].speed = 4.5;
+        data.animations.entries[3].curve = "overshot".to_string();
+        data.animations.beziers.push(Bezier {name: "overshot".to_string(), x0: 0.05, y0: 0.9, x1: 0.1, y1: 1.1});
+        for action in RuleAction::ALL {
+            for state in RuleState::ALL {
+                data.window_rules.push(WindowRule {
+                    action,
+                    arg: "2".to_string(),
+                    class: "^(firefox)$".to_string(),
+                    title: String::new(),
+                    floating: state,
+                    xwayland: state,
+                });
+            }
+        }
+        let first = toml::to_string(&data).unwrap();
+        let (again, warnings) = parse(&first);
+        assert_eq!(warnings, vec![]);
+        assert_eq!(again.binds, data.binds);
+        assert_eq!(again.widgets_left, data.widgets_left);
+        assert_eq!(again.monitors, data.monitors);
+        assert_eq!(again.input, data.input);
+        assert_eq!(again.window_rules, data.window_rules);
+        assert_eq!(again.animations, data.animations);
+        assert_eq!(toml::to_string(&again).unwrap(), first);
+    }
+    #[test]
+    fn unknown_values_are_reported() {
+        let file = MINIMAL
+            .replace(r#"primary = "alt""#, r#"primary = "hyper""#)
+            .replace(r#"blur = "n""#, r#"blur = "maybe""#)
+            .replace(r#"["System Tray", "Current User"]"#, r#"["System Tray", "Clcok", "Current User"]"#)
+            + r#"
+    [[binds]]
+    header = "pri"
+    mods = ["shift", "meta"]
+    key = "Q"
+    dispatcher = "killactive"
+
+    [[binds]]
+    header = "pri"
+    key = "W"
+    dispatcher = "teleport"
+    "#;
+        let (data, warnings) = parse(&file);
+        let keys: Vec<&str> = warnings.iter().map(|x| x.key.as_str()).collect();
+        assert_eq!(keys, vec!["primary", "blur", "widgets_right[1]", "binds[0].mods[1]", "binds[1].dispatcher"]);
+        assert_eq!(data.primary, ShortcutKey::Super);
+        assert!(data.blur);
+        assert_eq!(data.widgets_right, vec![BarWidget::Tray, BarWidget::User]);
+        assert_eq!(data.binds.len(), 1);
+        assert_eq!(data.binds[0].mods, vec![ShortcutKey::Shift]);
+    }
+    #[test]
+    fn first_layout_migrates() { //the example shipped before keys had underscores
+        let mut table: Table = from_str(include_str!("../tests/fixtures/cfg_v0.toml")).unwrap();
+        assert_eq!(migrate_cfg(&mut table), Some(0));
+        assert_eq!(migrate_cfg(&mut table), None);
+        let (data, warnings) = decode_cfg_table(table).unwrap();
+        assert_eq!(warnings, vec![]);
+        assert_eq!(data.theme, ThemeName::Dark);
+        assert_eq!(data.win_anim, WindowAnimation::PopIn);
+        assert_eq!(data.work_anim, WorkAnimation::SlideVert);
+        assert_eq!(data.widgets_left, FileData::default().widgets_left); //not in that layout at all
+        assert_eq!(data.binds, FileData::default().binds);
+    }
+    #[test]
+    fn fixed_binds_migrate() {
+        let (data, warnings) = parse(include_str!("../tests/fixtures/cfg_v1.toml"));
+        assert_eq!(warnings, vec![]);
+        assert_eq!(data.version, CFG_VERSION);
+        assert_eq!(data.binds.len(), 5);
+        assert_eq!(data.binds[0].header, BindKey::SecondaryKey);
+        assert_eq!(data.binds[0].key, "E");
+        assert_eq!(data.binds[0].arg, "wlogout");
+        assert_eq!(data.binds[2].dispatcher, Dispatcher::KillActive);
+        let written = toml::to_string(&data).unwrap();
+        assert!(!written.contains("exit_h"));
+        assert!(written.starts_with(&format!("version = {CFG_VERSION}\n")));
+    }
+    #[test]
+    fn newer_layout_is_flagged() {
+        let mut table: Table = from_str(&format!("version = {}\nfuture_key = 1\n", CFG_VERSION + 1)).unwrap();
+        assert_eq!(migrate_cfg(&mut table), None);
+        let (data, warnings) = decode_cfg_table(table).unwrap();
+        assert_eq!(data.version, CFG_VERSION + 1); //kept, so the save path can see it
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].key, "version");
+    }
+    #[test]
+    fn missing_sections_use_defaults() {
+        let (data, warnings) = parse("version = 2\ntheme = \"dark\"\n[border]\nwidth = 1\n");
+        assert_eq!(warnings, vec![]);
+        assert_eq!(data.theme, ThemeName::Dark);
+        assert_eq!(data.border.width, 1);
+        assert_eq!(data.border.gaps, Border::default().gaps);
+        assert_eq!(data.binds, FileData::default().binds);
+    }
+    #[test]
+    fn curves_are_told_apart() {
+        let mut animations = Animations::default();
+        for name in ["snappy", "snap", "snappy", "default"] {
+            animations.beziers.push(Bezier {name: name.to_string(), x0: 0.2, y0: 1.0, x1: 0.3, y1: 1.0});
+        }
+        animations.entry_mut(AnimationName::Fade).curve = "snappy".to_string();
+        assert_eq!(animations.curves(), vec!["default", "snappy", "snap"]);
+        assert!(animations.name_taken(1, "snappy"));
+        assert!(animations.name_taken(1, "default"));
+        assert!(!animations.name_taken(1, "snapp"));
+        assert_eq!(animations.curve_users(0), vec![5]);
+        assert!(animations.curve_users(2).is_empty()); //the repeat never drew anything
+    }
+    #[test]
+    fn preset_none_switches_off_the_tree() {
+        let animations = Animations::default();
+        for name in [AnimationName::Windows, AnimationName::WindowsOut] {
+            assert!(name.preset_off(WindowAnimation::None, WorkAnimation::Slide));
+            assert!(rip_animation(&animations, name, WindowAnimation::None, WorkAnimation::Slide).contains(",0,"));
+        }
+        for name in [AnimationName::Workspaces, AnimationName::SpecialWorkspace] {
+            assert!(name.preset_off(WindowAnimation::Slide, WorkAnimation::None));
+            assert!(!name.preset_off(WindowAnimation::None, WorkAnimation::Fade));
+        }
+        assert!(!AnimationName::Fade.preset_off(WindowAnimation::None, WorkAnimation::None));
+    }
+}
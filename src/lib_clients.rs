@@ -0,0 +1,76 @@
+#![allow(dead_code)]
+use std::io::ErrorKind;
+use std::process::Command;
+use serde_derive::Deserialize;
+use crate::lib_cfg::{CfgError, RuleAction, RuleState, WindowRule};
+
+//The windows open right now, read from hyprctl clients -j, so a window rule can start from one of them
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Client { //the fields of hyprctl's json we use, the rest is ignored
+    pub class: String,
+    pub title: String,
+    #[serde(default)]
+    pub floating: bool,
+    #[serde(default)]
+    pub xwayland: bool,
+}
+
+pub fn parse_clients(json: &str) -> Result<Vec<Client>, CfgError> { //windows without a class can't be matched on, so they are left out
+    let clients: Vec<Client> = serde_json::from_str(json).map_err(|e| CfgError::BadOutput("hyprctl clients".to_string(), e.to_string()))?;
+    let mut unique: Vec<Client> = vec![];
+    for client in clients {
+        if client.class.len() > 0 && !unique.iter().any(|x| x.class == client.class) { //one entry per application is enough
+            unique.push(client);
+        }
+    }
+    Ok(unique)
+}
+pub fn read_clients() -> Result<Vec<Client>, CfgError> { //only works inside a running hyprland session
+    let out = Command::new("hyprctl").args(["clients", "-j"]).output().map_err(|e| {
+        if e.kind() == ErrorKind::NotFound {
+            CfgError::MissingTool("hyprctl".to_string())
+        } else {
+            CfgError::Io("hyprctl".to_string(), e)
+        }
+    })?;
+    if !out.status.success() {
+        return Err(CfgError::BadOutput("hyprctl clients".to_string(), String::from_utf8_lossy(&out.stderr).trim().to_string()));
+    }
+    parse_clients(&String::from_utf8_lossy(&out.stdout))
+}
+pub fn exact_pattern(text: &str) -> String { //a regex matching only this text, classes like org.gnome.Nautilus have dots in them
+    let mut escaped = String::new();
+    for c in text.chars() {
+        if "\\.+*?()|[]{}^$".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    format!("^({escaped})$")
+}
+pub fn rule_from_client(client: &Client) -> WindowRule { //floats the application until the user picks something else
+    WindowRule {
+        action: RuleAction::Float,
+        arg: String::new(),
+        class: exact_pattern(&client.class),
+        title: String::new(),
+        floating: RuleState::Any,
+        xwayland: RuleState::Any,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hyprctl_clients_are_read() {
+        let clients = parse_clients(include_str!("../tests/fixtures/hyprctl_clients.json")).unwrap();
+        assert_eq!(clients.len(), 2); //the one without a class is left out
+        assert_eq!(clients[1], Client {class: "pavucontrol".to_string(), title: "Volume Control".to_string(), floating: true, xwayland: false});
+        assert_eq!(rule_from_client(&clients[1]).class, "^(pavucontrol)$");
+        assert_eq!(exact_pattern("org.gnome.Nautilus"), "^(org\\.gnome\\.Nautilus)$");
+        assert!(parse_clients("not json").is_err());
+    }
+}
@@ -0,0 +1,128 @@
+#![allow(dead_code)]
+
+//Line based diffs between two versions of a generated file, for showing what a restore or a save would change
+
+const CONTEXT: usize = 3; //unchanged lines shown around each change
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Same(String),
+    Added(String),
+    Removed(String),
+}
+
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> { //longest common subsequence, the files we compare are a few hundred lines at most
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1]; //common[i][j] is the lcs length of old[i..] and new[j..]
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+    let mut out = vec![];
+    let mut i = 0;
+    let mut j = 0;
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            out.push(DiffLine::Same(old[i].to_string()));
+            i = i + 1;
+            j = j + 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            out.push(DiffLine::Removed(old[i].to_string()));
+            i = i + 1;
+        } else {
+            out.push(DiffLine::Added(new[j].to_string()));
+            j = j + 1;
+        }
+    }
+    for line in &old[i..] {
+        out.push(DiffLine::Removed(line.to_string()));
+    }
+    for line in &new[j..] {
+        out.push(DiffLine::Added(line.to_string()));
+    }
+    out
+}
+pub fn has_changes(diff: &[DiffLine]) -> bool {
+    diff.iter().any(|x| !matches!(x, DiffLine::Same(..)))
+}
+pub fn unified_diff(path: &str, old: &str, new: &str) -> String { //the same layout as diff -u, empty when nothing changed
+    let diff = diff_lines(old, new);
+    if !has_changes(&diff) {
+        return String::new();
+    }
+    let mut near = vec![false; diff.len()]; //lines that are changed or within CONTEXT of a change
+    for i in 0..diff.len() {
+        if !matches!(diff[i], DiffLine::Same(..)) {
+            let start = i.saturating_sub(CONTEXT);
+            let end = (i + CONTEXT + 1).min(diff.len());
+            for j in start..end {
+                near[j] = true;
+            }
+        }
+    }
+    let mut old_seen = vec![0; diff.len() + 1]; //how many lines of each side come before diff[i]
+    let mut new_seen = vec![0; diff.len() + 1];
+    for i in 0..diff.len() {
+        old_seen[i + 1] = old_seen[i] + if matches!(diff[i], DiffLine::Added(..)) {0} else {1};
+        new_seen[i + 1] = new_seen[i] + if matches!(diff[i], DiffLine::Removed(..)) {0} else {1};
+    }
+    let mut out = format!("--- {path}\n+++ {path}\n");
+    let mut i = 0;
+    while i < diff.len() {
+        if !near[i] {
+            i = i + 1;
+            continue;
+        }
+        let start = i;
+        while i < diff.len() && near[i] {
+            i = i + 1;
+        }
+        let old_count = old_seen[i] - old_seen[start];
+        let new_count = new_seen[i] - new_seen[start];
+        let old_start = if old_count == 0 {old_seen[start]} else {old_seen[start] + 1}; //an empty side points at the line before it
+        let new_start = if new_count == 0 {new_seen[start]} else {new_seen[start] + 1};
+        out = format!("{out}@@ -{old_start},{old_count} +{new_start},{new_count} @@\n");
+        for line in &diff[start..i] {
+            out = match line {
+                DiffLine::Same(x) => format!("{out} {x}\n"),
+                DiffLine::Added(x) => format!("{out}+{x}\n"),
+                DiffLine::Removed(x) => format!("{out}-{x}\n"),
+            };
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changed_lines_are_marked() {
+        let diff = diff_lines("gaps = 5\nwidth = 2\nblur = y\n", "gaps = 5\nwidth = 3\nblur = y\nrounding = 10\n");
+        assert_eq!(diff, vec![
+            DiffLine::Same("gaps = 5".to_string()),
+            DiffLine::Removed("width = 2".to_string()),
+            DiffLine::Added("width = 3".to_string()),
+            DiffLine::Same("blur = y".to_string()),
+            DiffLine::Added("rounding = 10".to_string()),
+        ]);
+        assert!(has_changes(&diff));
+        assert!(!has_changes(&diff_lines("a\nb\n", "a\nb\n")));
+        assert_eq!(diff_lines("", "a\n"), vec![DiffLine::Added("a".to_string())]);
+    }
+    #[test]
+    fn unified_diffs_match_diff_u() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+        let new = "1\n2\n3\n4\nfive\n6\n7\n8\n9\n10\n11\n";
+        assert_eq!(unified_diff("a.conf", old, new), "--- a.conf\n+++ a.conf\n@@ -2,9 +2,10 @@\n 2\n 3\n 4\n-5\n+five\n 6\n 7\n 8\n 9\n 10\n+11\n");
+        assert_eq!(unified_diff("a.conf", "", "x\n"), "--- a.conf\n+++ a.conf\n@@ -0,0 +1,1 @@\n+x\n");
+        assert_eq!(unified_diff("a.conf", old, old), "");
+    }
+}
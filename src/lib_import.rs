@@ -0,0 +1,817 @@
+#![allow(dead_code)]
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use gettextrs::gettext as tr;
+use crate::lib_cfg::*;
+use crate::lib_monitors::parse_mode;
+use crate::lib_render::usercfg_path;
+
+//The importer, reads a hand-written hyprland.conf into our settings and keeps whatever it doesn't understand for usercfg.conf
+
+const SOURCE_DEPTH: usize = 10; //files sourcing each other in a loop stop here
+const OUR_VARS: [&str; 2] = ["mainMod", "secMod"]; //defined by the hyprland.conf we generate
+
+#[derive(Debug, Clone)]
+pub struct Imported {
+    pub data: FileData,
+    pub leftover: String, //hyprland.conf syntax for everything that wasn't recognised, empty when nothing was left
+    pub warnings: Vec<CfgWarning>,
+}
+#[derive(Debug, Clone)]
+struct ConfLine {
+    section: Vec<String>, //the sections the line is nested in, outermost first
+    key: String,
+    submap: String, //the submap the line belongs to, empty outside of one
+    value: String, //with variables filled in
+    raw: String, //as it was written, for usercfg.conf
+}
+
+pub fn import_hyprland(path: &str, base: FileData) -> Result<Imported, CfgError> { //base supplies everything a hyprland.conf can't, like the theme
+    let contents = fs::read_to_string(path).map_err(|e| CfgError::Io(path.to_string(), e))?;
+    let dir = Path::new(path).parent().map(|x| x.display().to_string()).unwrap_or_default();
+    Ok(parse_hyprland(&contents, &dir, base))
+}
+pub fn parse_hyprland(contents: &str, dir: &str, base: FileData) -> Imported {
+    let mut lines = vec![];
+    let mut vars = vec![];
+    let mut warnings = vec![];
+    collect_lines(contents, dir, 0, &mut vars, &mut lines, &mut warnings);
+    let mut data = base;
+    data.binds = vec![];
+    data.autostart = vec![];
+    data.monitors = vec![];
+    data.window_rules = vec![];
+    data.animations = Animations::default(); //speeds and curves the file leaves out are hyprland's own
+    let (pri, sec) = guess_shortcuts(&lines, &vars, data.primary, data.secondary);
+    data.primary = pri;
+    data.secondary = sec;
+    data.workspaces = guess_workspaces(&lines, pri, sec);
+    data.directions = guess_directions(&lines, pri, sec, data.directions);
+    data.layout = guess_layout(&lines, data.layout);
+    let inferred = inferred_combos(Some(pri), Some(sec), &data.workspaces, &data.directions, &data.layout);
+    let built_in = built_in_binds(&data, pri, sec);
+    let gaps_in = lines.iter().find(|x| full_key(x) == "general:gaps_in").and_then(|x| first_int(&x.value));
+    let mut leftover = vec![];
+    for line in lines {
+        let value = line.value.as_str();
+        let recognised = match full_key(&line).as_str() {
+            _ if line.submap.len() > 0 => data.directions.submap && line.submap == RESIZE_SUBMAP, //our resize mode as a whole, other submaps stay as written
+            "general:gaps_in" => set_int(value, &mut data.border.gaps),
+            "general:gaps_out" => gaps_in.is_some() && first_int(value) == gaps_in, //we always write both the same
+            "general:border_size" => set_int(value, &mut data.border.width),
+            "decoration:rounding" => set_int(value, &mut data.border.radius),
+            "decoration:blur:enabled" | "decoration:blur" => set_bool(value, &mut data.blur),
+            "general:layout" => match decode(value) {
+                Some(engine) => {
+                    data.layout.engine = engine;
+                    true
+                }
+                None => false
+            },
+            "dwindle:pseudotile" => set_bool(value, &mut data.layout.pseudotile),
+            "dwindle:preserve_split" => set_bool(value, &mut data.layout.preserve_split),
+            "master:orientation" => match decode(value) {
+                Some(orientation) => {
+                    data.layout.orientation = orientation;
+                    true
+                }
+                None => false
+            },
+            "master:new_is_master" => set_bool(value, &mut data.layout.new_is_master),
+            "input:kb_layout" => set_string(value, &mut data.input.kb_layout),
+            "input:kb_variant" => set_string(value, &mut data.input.kb_variant),
+            "input:repeat_rate" => set_int(value, &mut data.input.repeat_rate),
+            "input:repeat_delay" => set_int(value, &mut data.input.repeat_delay),
+            "input:sensitivity" => match value.parse() {
+                Ok(x) => {
+                    data.input.sensitivity = x;
+                    true
+                }
+                Err(..) => false
+            },
+            "input:natural_scroll" => set_bool(value, &mut data.input.natural_scroll),
+            "input:touchpad:natural_scroll" => set_bool(value, &mut data.input.touchpad_natural_scroll),
+            "input:touchpad:tap-to-click" => set_bool(value, &mut data.input.tap_to_click),
+            "input:follow_mouse" => match value.parse::<usize>().ok().and_then(|x| FollowMouse::ALL.get(x)) {
+                Some(follow_mouse) => {
+                    data.input.follow_mouse = *follow_mouse;
+                    true
+                }
+                None => false
+            },
+            "animations:enabled" => match parse_bool(value) {
+                Some(false) => {
+                    data.win_anim = WindowAnimation::None;
+                    data.work_anim = WorkAnimation::None;
+                    true
+                }
+                Some(true) => true,
+                None => false
+            },
+            "animations:bezier" => import_bezier(value, &mut data.animations),
+            "animations:animation" => import_animation(value, &mut data),
+            "bind" => match import_bind(value, pri, sec) {
+                Some(bind) => {
+                    let combo = rip_combo(&bind, Some(pri), Some(sec));
+                    if inferred.contains(&combo) { //we write these ourselves, only worth a warning if it did something else
+                        if !built_in.contains(&bind) { //e.g. a workspace key sending to another workspace
+                            warnings.push(CfgWarning {
+                                key: "bind".to_string(),
+                                message: format!("{}: {}", line.raw, tr("left out, the same keys are used by a built-in binding")),
+                            });
+                        }
+                    } else {
+                        data.binds.push(bind);
+                    }
+                    true
+                }
+                None => false
+            },
+            "binde" => match import_bind(value, pri, sec) { //only our resize binds, a repeating bind of the user's has nowhere to go
+                Some(bind) => bind.dispatcher == Dispatcher::ResizeActive && inferred.contains(&rip_combo(&bind, Some(pri), Some(sec))),
+                None => false
+            },
+            "monitor" => match import_monitor(value) {
+                Some(monitor) => {
+                    data.monitors.push(monitor);
+                    true
+                }
+                None => false
+            },
+            "workspace" => match value.split_once(',') { //only the plain workspace-to-monitor rule, the rest stays as written
+                Some((workspace, rule)) if rule.trim().starts_with("monitor:") && !rule.contains(',') => match workspace.trim().parse() {
+                    Ok(workspace) => {
+                        data.workspaces.set_monitor(workspace, rule.trim()["monitor:".len()..].to_string());
+                        true
+                    }
+                    Err(..) => false
+                },
+                _ => false
+            },
+            "windowrulev2" => match import_window_rule(value) {
+                Some(rule) => {
+                    data.window_rules.push(rule);
+                    true
+                }
+                None => false
+            },
+            "exec-once" => {
+                if value != "oceania-shell" && !value.ends_with("/hypr/autostart") { //started by the config we generate anyway
+                    data.autostart.push(AutostartEntry {
+                        command: value.to_string(),
+                        enabled: true,
+                    });
+                }
+                true
+            }
+            _ => false
+        };
+        if !recognised {
+            leftover.push(line);
+        }
+    }
+    Imported {
+        data,
+        leftover: write_leftover(&vars, &leftover),
+        warnings,
+    }
+}
+pub fn usercfg_with(home: &str, leftover: &str) -> Result<Option<String>, CfgError> { //usercfg.conf with the leftover appended, None when there is nothing new to add
+    let path = usercfg_path(home);
+    let current = match fs::read_to_string(&path) {
+        Ok(current) => current,
+        Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(CfgError::Io(path, e))
+    };
+    Ok(append_leftover(&current, leftover))
+}
+fn append_leftover(current: &str, leftover: &str) -> Option<String> { //what the user already has there stays, an import done twice only adds its lines once
+    if leftover.trim().len() == 0 || current.contains(leftover.trim_end()) {
+        None
+    } else if current.len() > 0 && !current.ends_with('\n') {
+        Some(format!("{current}\n{leftover}"))
+    } else {
+        Some(format!("{current}{leftover}"))
+    }
+}
+fn collect_lines(contents: &str, dir: &str, depth: usize, vars: &mut Vec<(String, String)>, lines: &mut Vec<ConfLine>, warnings: &mut Vec<CfgWarning>) {
+    let mut section: Vec<String> = vec![];
+    let mut submap = String::new();
+    for line in contents.lines() {
+        let line = strip_comment(line);
+        let line = line.trim();
+        if line.len() == 0 {
+            continue;
+        }
+        if line == "}" {
+            section.pop();
+            continue;
+        }
+        if let Some(name) = line.strip_suffix('{') {
+            section.push(name.trim().to_string());
+            continue;
+        }
+        let (key, value) = match line.split_once('=') {
+            Some((key, value)) => (key.trim(), value.trim()),
+            None => {
+                warnings.push(CfgWarning {
+                    key: line.to_string(),
+                    message: tr("not a hyprland.conf line, left out"),
+                });
+                continue;
+            }
+        };
+        let value = fill_vars(value, vars);
+        if key.starts_with('$') {
+            vars.push((key[1..].to_string(), value));
+            continue;
+        }
+        if key == "source" && section.len() == 0 {
+            let path = resolve_path(&value, dir);
+            if path.ends_with("/hypr/usercfg.conf") { //our own, it keeps being sourced
+                continue;
+            }
+            let sourced = if depth < SOURCE_DEPTH {
+                fs::read_to_string(&path).ok()
+            } else {
+                None
+            };
+            match sourced {
+                Some(sourced) => {
+                    let sourced_dir = Path::new(&path).parent().map(|x| x.display().to_string()).unwrap_or_default();
+                    collect_lines(&sourced, &sourced_dir, depth + 1, vars, lines, warnings);
+                    continue;
+                }
+                None => warnings.push(CfgWarning {
+                    key: "source".to_string(),
+                    message: format!("{path}: {}", tr("could not be read, it is still sourced from usercfg.conf")),
+                })
+            }
+        }
+        let line_submap = if key == "submap" && section.len() == 0 { //the reset line still belongs to the submap it closes
+            let closed = submap.clone();
+            submap = if value == "reset" {String::new()} else {value.clone()};
+            if value == "reset" {closed} else {value.clone()}
+        } else {
+            submap.clone()
+        };
+        lines.push(ConfLine {
+            section: section.clone(),
+            key: key.to_string(),
+            submap: line_submap,
+            value,
+            raw: line.to_string(),
+        });
+    }
+}
+fn strip_comment(line: &str) -> String { //# starts a comment, ## is a literal #
+    let mut out = String::new();
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '#' {
+            if chars.peek() == Some(&'#') {
+                chars.next();
+                out.push_str("##"); //kept escaped, the raw line goes back into a config
+            } else {
+                break;
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+fn fill_vars(value: &str, vars: &[(String, String)]) -> String {
+    let mut sorted: Vec<&(String, String)> = vars.iter().collect();
+    sorted.sort_by_key(|x| std::cmp::Reverse(x.0.len())); //$mainModShift before $mainMod
+    let mut out = value.replace("##", "#");
+    for (name, replacement) in sorted {
+        out = out.replace(&format!("${name}"), replacement);
+    }
+    out
+}
+fn resolve_path(path: &str, dir: &str) -> String {
+    if let Some(rest) = path.strip_prefix("~/") {
+        format!("{}/{rest}", std::env::var("HOME").unwrap_or_default())
+    } else if path.starts_with('/') {
+        path.to_string()
+    } else {
+        format!("{dir}/{path}")
+    }
+}
+fn full_key(line: &ConfLine) -> String { //decoration { blur { enabled } } and decoration:blur:enabled are the same option
+    let mut parts = line.section.clone();
+    parts.push(line.key.clone());
+    parts.join(":")
+}
+fn first_int(value: &str) -> Option<i32> { //gaps can be given per side, we only keep one
+    value.split_whitespace().next()?.parse().ok()
+}
+fn set_int(value: &str, field: &mut i32) -> bool {
+    match first_int(value) {
+        Some(x) => {
+            *field = x;
+            true
+        }
+        None => false
+    }
+}
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Some(true),
+        "false" | "no" | "off" | "0" => Some(false),
+        _ => None
+    }
+}
+fn set_bool(value: &str, field: &mut bool) -> bool {
+    match parse_bool(value) {
+        Some(x) => {
+            *field = x;
+            true
+        }
+        None => false
+    }
+}
+fn set_string(value: &str, field: &mut String) -> bool { //several layouts are a comma separated list, kept as one
+    *field = value.to_string();
+    true
+}
+fn parse_mod(name: &str) -> Option<ShortcutKey> {
+    let name = name.to_uppercase();
+    match name.as_str() {
+        "CTRL" => Some(ShortcutKey::Ctrl),
+        "WIN" | "LOGO" | "MOD4" => Some(ShortcutKey::Super),
+        "MOD1" => Some(ShortcutKey::Alt),
+        _ => ShortcutKey::ALL.iter().find(|x| rip_shortcut(Some(**x)) == name).copied()
+    }
+}
+fn parse_mods(value: &str) -> Option<Vec<ShortcutKey>> { //None if any of them is one we can't store
+    let mut mods = vec![];
+    for name in value.split(|c: char| c == '_' || c.is_whitespace()) {
+        if name.len() > 0 {
+            mods.push(parse_mod(name)?);
+        }
+    }
+    Some(mods)
+}
+fn import_bind(value: &str, pri: ShortcutKey, sec: ShortcutKey) -> Option<Keybind> {
+    let parts: Vec<&str> = value.splitn(4, ',').map(|x| x.trim()).collect();
+    if parts.len() < 3 || parts[1].len() == 0 {
+        return None;
+    }
+    let mut mods = parse_mods(parts[0])?;
+    let dispatcher = *Dispatcher::ALL.iter().find(|x| rip_dispatcher(**x) == parts[2])?;
+    let has_pri = mods.contains(&pri);
+    let has_sec = mods.contains(&sec);
+    let header = match (has_pri, has_sec) {
+        (true, true) => BindKey::BothKey,
+        (true, false) => BindKey::PrimaryKey,
+        (false, true) => BindKey::SecondaryKey,
+        (false, false) => BindKey::NoKey
+    };
+    mods.retain(|x| (*x != pri || !has_pri) && (*x != sec || !has_sec));
+    Some(Keybind {
+        header,
+        mods,
+        key: unrip_key(parts[1]),
+        dispatcher,
+        arg: parts.get(3).map(|x| x.to_string()).unwrap_or_default(),
+    })
+}
+fn guess_shortcuts(lines: &[ConfLine], vars: &[(String, String)], pri: ShortcutKey, sec: ShortcutKey) -> (ShortcutKey, ShortcutKey) { //$mainMod if there is one, otherwise whatever most binds start with
+    let mut counts = vec![0; ShortcutKey::ALL.len()];
+    for line in lines {
+        if full_key(line) != "bind" {
+            continue;
+        }
+        let header = line.value.split(',').next().unwrap_or_default();
+        if let Some(first) = header.split(|c: char| c == '_' || c.is_whitespace()).find(|x| x.len() > 0).and_then(parse_mod) {
+            counts[ShortcutKey::ALL.iter().position(|x| *x == first).unwrap()] += 1;
+        }
+    }
+    let main_mod = vars.iter().find(|x| x.0.eq_ignore_ascii_case("mainMod")).and_then(|x| parse_mod(x.1.trim()));
+    let sec_mod = vars.iter().find(|x| x.0.eq_ignore_ascii_case("secMod")).and_then(|x| parse_mod(x.1.trim()));
+    let most_used = (0..counts.len()).filter(|x| counts[*x] > 0).max_by_key(|x| counts[*x]).map(|x| ShortcutKey::ALL[x]);
+    let pri = main_mod.or(most_used).unwrap_or(pri);
+    let sec = sec_mod.unwrap_or(sec);
+    let sec = if sec == pri { //our defaults may not fit any more
+        *ShortcutKey::ALL.iter().find(|x| **x != pri && **x != ShortcutKey::Ctrl).unwrap()
+    } else {
+        sec
+    };
+    (pri, sec)
+}
+fn import_monitor(value: &str) -> Option<MonitorCfg> { //name, WxH@R, XxY, scale and maybe a transform, preferred and auto are kept as written
+    let parts: Vec<&str> = value.split(',').map(|x| x.trim()).collect();
+    if (parts.len() != 4 && parts.len() != 6) || parts[0].len() == 0 {
+        return None;
+    }
+    let (width, height, refresh) = parse_mode(parts[1])?;
+    let (x, y) = parts[2].split_once('x')?;
+    let transform = if parts.len() == 6 {
+        if parts[4] != "transform" {
+            return None;
+        }
+        *Transform::ALL.get(parts[5].parse::<usize>().ok()?)?
+    } else {
+        Transform::Normal
+    };
+    Some(MonitorCfg {
+        name: parts[0].to_string(),
+        width,
+        height,
+        refresh,
+        x: x.parse().ok()?,
+        y: y.parse().ok()?,
+        scale: parts[3].parse().ok()?,
+        transform,
+    })
+}
+fn import_window_rule(value: &str) -> Option<WindowRule> { //only the actions and conditions the window rules page has
+    let mut parts = value.split(',').map(|x| x.trim());
+    let head = parts.next()?;
+    let (action, arg) = match head.split_once(' ') {
+        Some((action, arg)) => (action, arg.trim()),
+        None => (head, "")
+    };
+    let mut rule = WindowRule {
+        action: decode(action)?,
+        arg: arg.to_string(),
+        ..WindowRule::default()
+    };
+    if rule.action.takes_arg() == (arg.len() == 0) {
+        return None;
+    }
+    for condition in parts {
+        let (name, pattern) = condition.split_once(':')?;
+        let state = match parse_bool(pattern.trim()) {
+            Some(true) => RuleState::Yes,
+            Some(false) => RuleState::No,
+            None => RuleState::Any
+        };
+        match name.trim() {
+            "class" if rule.class.len() == 0 => rule.class = pattern.trim().to_string(),
+            "title" if rule.title.len() == 0 => rule.title = pattern.trim().to_string(),
+            "floating" if state != RuleState::Any => rule.floating = state,
+            "xwayland" if state != RuleState::Any => rule.xwayland = state,
+            _ => return None
+        }
+    }
+    rip_window_rule(&rule).map(|_| rule) //a rule we couldn't write back isn't one of ours
+}
+fn guess_workspaces(lines: &[ConfLine], pri: ShortcutKey, sec: ShortcutKey) -> Workspaces { //the longest row of workspace binds counting up from 1, then whether moving follows and whether cycling is there
+    let mut binds = vec![];
+    for line in lines {
+        if line.section.len() == 0 && line.submap.len() == 0 && line.key == "bind" {
+            if let Some(bind) = import_bind(&line.value, pri, sec) {
+                binds.push(bind);
+            }
+        }
+    }
+    let found = |header: BindKey, key: &str, dispatcher: Dispatcher, arg: &str| binds.iter().any(|x| x.header == header && x.mods.len() == 0 && x.key == key && x.dispatcher == dispatcher && x.arg == arg);
+    let mut workspaces = Workspaces::default(); //a file without any keeps our usual ten
+    let mut best = 0;
+    for keys in WorkspaceKeys::ALL {
+        let row = keys.keys();
+        let mut count = 0;
+        while count < row.len() && found(BindKey::PrimaryKey, &row[count], Dispatcher::Workspace, &(count + 1).to_string()) {
+            count = count + 1;
+        }
+        if count > best {
+            best = count;
+            workspaces.keys = keys;
+            workspaces.count = count as u8;
+        }
+    }
+    let row = workspaces.keys.keys();
+    workspaces.follow = best > 0 && (0..best).all(|i| found(BindKey::BothKey, &row[i], Dispatcher::MoveToWorkspace, &(i + 1).to_string()));
+    workspaces.cycle = (0..CYCLE_KEYS.len()).any(|i| found(BindKey::PrimaryKey, CYCLE_KEYS[i], Dispatcher::Workspace, if i % 2 == 0 {"e+1"} else {"e-1"}));
+    workspaces
+}
+fn built_in_binds(data: &FileData, pri: ShortcutKey, sec: ShortcutKey) -> Vec<Keybind> { //the direction, workspace and layout binds render_hyprland writes, read back like any other bind
+    let written = format!("{}{}{}", rip_directions(&data.directions, Some(pri)), rip_workspaces(&data.workspaces), rip_layout_binds(&data.layout));
+    let mut binds = vec![];
+    for line in written.lines() {
+        if let Some((key, value)) = line.split_once('=') {
+            if key.trim() == "bind" {
+                let value = value.replace("$mainMod", &rip_shortcut(Some(pri))).replace("$secMod", &rip_shortcut(Some(sec)));
+                if let Some(bind) = import_bind(&value, pri, sec) {
+                    binds.push(bind);
+                }
+            }
+        }
+    }
+    binds
+}
+fn guess_directions(lines: &[ConfLine], pri: ShortcutKey, sec: ShortcutKey, base: Directions) -> Directions { //which keys movefocus is on, then whether our resize binds and resize mode are there
+    let mut binds = vec![]; //bind or binde, the submap and the binding
+    for line in lines {
+        if line.section.len() == 0 && (line.key == "bind" || line.key == "binde") {
+            if let Some(bind) = import_bind(&line.value, pri, sec) {
+                binds.push((line.key.as_str(), line.submap.as_str(), bind));
+            }
+        }
+    }
+    let found = |kind: &str, submap: &str, bind: &Keybind| binds.iter().any(|x| x.0 == kind && x.1 == submap && x.2 == *bind);
+    let step = |header: BindKey, mods: Vec<ShortcutKey>, key: &str, dispatcher: Dispatcher, arg: &str| Keybind {
+        header,
+        mods,
+        key: key.to_string(),
+        dispatcher,
+        arg: arg.to_string(),
+    };
+    let mut directions = Directions {
+        resize: false,
+        submap: false,
+        ..base
+    };
+    for keys in DirectionKeys::ALL {
+        if (0..DIRECTIONS.len()).all(|i| found("bind", "", &step(BindKey::PrimaryKey, vec![], keys.keys()[i], Dispatcher::MoveFocus, DIRECTIONS[i]))) {
+            directions.keys = keys;
+            break;
+        }
+    }
+    let keys = directions.keys.keys();
+    for modifier in ShortcutKey::ALL {
+        if (0..DIRECTIONS.len()).all(|i| found("binde", "", &step(BindKey::PrimaryKey, vec![modifier], keys[i], Dispatcher::ResizeActive, RESIZE_STEPS[i]))) {
+            directions.resize = true;
+            directions.resize_mod = modifier;
+            break;
+        }
+    }
+    let mut expected = vec![("bind", step(BindKey::NoKey, vec![], "Escape", Dispatcher::Submap, "reset"))];
+    for i in 0..DIRECTIONS.len() {
+        expected.push(("binde", step(BindKey::NoKey, vec![], keys[i], Dispatcher::ResizeActive, RESIZE_STEPS[i])));
+    }
+    let inside = lines.iter().filter(|x| x.submap == RESIZE_SUBMAP && x.key != "submap").count();
+    if inside == expected.len() && expected.iter().all(|x| found(x.0, RESIZE_SUBMAP, &x.1)) { //anything more and it isn't the one we write
+        directions.submap = true;
+        if let Some(enter) = binds.iter().find(|x| x.0 == "bind" && x.1.len() == 0 && x.2.header == BindKey::PrimaryKey && x.2.mods.len() == 0 && x.2.dispatcher == Dispatcher::Submap && x.2.arg == RESIZE_SUBMAP) {
+            directions.submap_key = enter.2.key.clone();
+        }
+    }
+    directions
+}
+fn guess_layout(lines: &[ConfLine], base: Layout) -> Layout { //the engine and pseudotiling decide which binds are ours, so they are needed before the binds are read
+    let mut layout = base;
+    for line in lines {
+        match full_key(line).as_str() {
+            "general:layout" => if let Some(engine) = decode(&line.value) {layout.engine = engine},
+            "dwindle:pseudotile" => if let Some(x) = parse_bool(&line.value) {layout.pseudotile = x},
+            _ => {}
+        }
+    }
+    layout
+}
+fn import_bezier(value: &str, animations: &mut Animations) -> bool { //a name and the four numbers of its control points
+    let parts: Vec<&str> = value.split(',').map(|x| x.trim()).collect();
+    if parts.len() != 5 || parts[0].len() == 0 || animations.curves().iter().any(|x| x == parts[0]) {
+        return false;
+    }
+    let mut points = [0.0; 4];
+    for i in 0..4 {
+        points[i] = match parts[i + 1].parse() {
+            Ok(x) => x,
+            Err(..) => return false
+        };
+    }
+    animations.beziers.push(Bezier {
+        name: parts[0].to_string(),
+        x0: points[0],
+        y0: points[1],
+        x1: points[2],
+        y1: points[3],
+    });
+    true
+}
+fn import_animation(value: &str, data: &mut FileData) -> bool { //only the trees and styles we offer, anything else is kept as written
+    let parts: Vec<&str> = value.split(',').map(|x| x.trim()).collect();
+    if parts.len() < 2 {
+        return false;
+    }
+    let name: AnimationName = match decode(parts[0]) {
+        Some(name) => name,
+        None => return false
+    };
+    let enabled = parts[1] != "0";
+    let speed = match parts.get(2).map(|x| x.parse::<f64>()) {
+        Some(Ok(x)) if x > 0.0 => x,
+        Some(..) => return false,
+        None => AnimationCfg::new(name).speed
+    };
+    let curve = parts.get(3).copied().unwrap_or("default");
+    if !data.animations.curves().iter().any(|x| x == curve) { //hyprland would complain about it too
+        return false;
+    }
+    let style = parts.get(4).copied().unwrap_or_default();
+    match name {
+        AnimationName::Windows => {
+            let popin = style.strip_prefix("popin").map(|x| x.trim().trim_end_matches('%'));
+            data.win_anim = match (enabled, style, popin) {
+                (false, ..) => WindowAnimation::None,
+                (true, "", _) | (true, "popin", _) => WindowAnimation::PopIn,
+                (true, "slide", _) => WindowAnimation::Slide,
+                (true, _, Some(percent)) => match percent.parse() {
+                    Ok(percent) => {
+                        data.animations.popin = percent;
+                        WindowAnimation::PopIn
+                    }
+                    Err(..) => return false
+                },
+                _ => return false
+            };
+        }
+        AnimationName::Workspaces => {
+            data.work_anim = match (enabled, style) {
+                (false, _) => WorkAnimation::None,
+                (true, "slide") | (true, "") => WorkAnimation::Slide,
+                (true, "slidevert") => WorkAnimation::SlideVert,
+                (true, "fade") => WorkAnimation::Fade,
+                _ => return false
+            };
+        }
+        AnimationName::WindowsOut | AnimationName::SpecialWorkspace => { //these follow the preset, written with the same style
+            let preset = if name == AnimationName::WindowsOut {rip_win_anim(data.win_anim, data.animations.popin)} else {rip_work_anim(data.work_anim)};
+            match preset {
+                Some(preset) if enabled && (style.len() == 0 || style == preset) => data.animations.entry_mut(name).enabled = true,
+                Some(..) if !enabled => data.animations.entry_mut(name).enabled = false,
+                None if !enabled => {}
+                _ => return false
+            }
+        }
+        AnimationName::Border | AnimationName::Fade | AnimationName::Layers => {
+            if style.len() > 0 {
+                return false;
+            }
+            data.animations.entry_mut(name).enabled = enabled;
+        }
+    }
+    let entry = data.animations.entry_mut(name);
+    entry.speed = speed;
+    entry.curve = curve.to_string();
+    true
+}
+fn write_leftover(vars: &[(String, String)], lines: &[ConfLine]) -> String { //reopens the sections each line came from
+    if lines.len() == 0 {
+        return String::new();
+    }
+    let mut out = format!("# {}\n", tr("imported by cuttlefish, settings it could not take over"));
+    for (name, value) in vars {
+        if !OUR_VARS.contains(&name.as_str()) {
+            out = format!("{out}${name} = {value}\n");
+        }
+    }
+    let mut open: Vec<String> = vec![];
+    for line in lines {
+        let shared = open.iter().zip(&line.section).take_while(|(a, b)| a == b).count();
+        while open.len() > shared {
+            open.pop();
+            out = format!("{out}{}}}\n", "    ".repeat(open.len()));
+        }
+        while open.len() < line.section.len() {
+            out = format!("{out}{}{} {{\n", "    ".repeat(open.len()), line.section[open.len()]);
+            open.push(line.section[open.len()].clone());
+        }
+        out = format!("{out}{}{}\n", "    ".repeat(open.len()), line.raw);
+    }
+    while open.len() > 0 {
+        open.pop();
+        out = format!("{out}{}}}\n", "    ".repeat(open.len()));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib_render::{Palette, RenderInput, render_hyprland};
+
+    #[test]
+    fn hand_written_config_imports() {
+        let imported = parse_hyprland(include_str!("../tests/fixtures/hyprland_hand_written.conf"), "/nonexistent", FileData::default());
+        let data = &imported.data;
+        assert_eq!(data.primary, ShortcutKey::Alt); //from $mainMod
+        assert_eq!(data.border.gaps, 8);
+        assert_eq!(data.border.width, 3);
+        assert_eq!(data.border.radius, 6);
+        assert!(!data.blur);
+        assert_eq!(data.win_anim, WindowAnimation::Slide);
+        assert_eq!(data.work_anim, WorkAnimation::Fade);
+        assert_eq!(data.animations.beziers, vec![Bezier {name: "myBezier".to_string(), x0: 0.05, y0: 0.9, x1: 0.1, y1: 1.05}]);
+        assert_eq!((data.animations.entry(AnimationName::Windows).speed, data.animations.entry(AnimationName::Windows).curve.as_str()), (7.0, "myBezier"));
+        assert_eq!(data.animations.entry(AnimationName::Workspaces).speed, 6.0);
+        assert_eq!(data.secondary, ShortcutKey::Shift);
+        assert_eq!(data.autostart, vec![AutostartEntry {command: "nm-applet --indicator".to_string(), enabled: true}]);
+        assert_eq!(data.binds, vec![
+            Keybind {header: BindKey::PrimaryKey, mods: vec![], key: "Enter".to_string(), dispatcher: Dispatcher::Exec, arg: "kitty".to_string()},
+            Keybind {header: BindKey::BothKey, mods: vec![], key: "Q".to_string(), dispatcher: Dispatcher::KillActive, arg: String::new()},
+            Keybind {header: BindKey::NoKey, mods: vec![ShortcutKey::Ctrl], key: "Snapshot".to_string(), dispatcher: Dispatcher::Exec, arg: "grim -g \"$(slurp)\" - | wl-copy".to_string()},
+            Keybind {header: BindKey::PrimaryKey, mods: vec![ShortcutKey::Mod3], key: "T".to_string(), dispatcher: Dispatcher::Exec, arg: "kitty".to_string()},
+            Keybind {header: BindKey::PrimaryKey, mods: vec![], key: "M".to_string(), dispatcher: Dispatcher::Submap, arg: "media".to_string()},
+        ]);
+        assert_eq!(imported.warnings.len(), 3); //the built-in bindings that close a window and go to another workspace instead, and the missing source
+        assert_eq!((data.workspaces.count, data.workspaces.keys, data.workspaces.follow, data.workspaces.cycle), (3, WorkspaceKeys::Numbers, false, false));
+        assert_eq!(imported.leftover, include_str!("../tests/fixtures/hyprland_hand_written.leftover"));
+        assert_eq!(data.directions.keys, DirectionKeys::Vim);
+        assert!(!data.directions.submap); //the media submap isn't ours
+        assert_eq!(data.input.kb_layout, "us,de");
+        assert_eq!(data.layout.engine, LayoutEngine::Dwindle);
+        assert_eq!(data.window_rules, vec![WindowRule {action: RuleAction::Float, class: "^(pavucontrol)$".to_string(), ..WindowRule::default()}]);
+        assert!(data.input.touchpad_natural_scroll);
+        assert_eq!(data.input.follow_mouse, FollowMouse::Click);
+        assert!(find_conflicts(&data.binds, Some(data.primary), Some(data.secondary), &data.workspaces, &data.directions, &data.layout).is_empty());
+    }
+    #[test]
+    fn workspaces_come_from_the_file() { //not from whatever was set before the import
+        let mut base = FileData::default();
+        base.workspaces.count = 5;
+        base.workspaces.follow = true;
+        base.workspaces.cycle = true;
+        let mut conf = String::new();
+        for i in 1..=10 {
+            conf = format!("{conf}bind = SUPER, F{i}, workspace, {i}\nbind = SUPER SHIFT, F{i}, movetoworkspacesilent, {i}\n");
+        }
+        let imported = parse_hyprland(&conf, "/nonexistent", base);
+        assert_eq!((imported.data.workspaces.count, imported.data.workspaces.keys), (10, WorkspaceKeys::Function));
+        assert!(!imported.data.workspaces.follow);
+        assert!(!imported.data.workspaces.cycle);
+        assert_eq!(imported.data.binds, vec![]);
+        assert_eq!(imported.warnings, vec![]);
+    }
+    #[test]
+    fn leftover_is_appended_once() {
+        let leftover = "# imported\nbind = $mainMod, F, fakefullscreen\n";
+        let once = append_leftover("source = ~/extra.conf", leftover).unwrap();
+        assert_eq!(once, format!("source = ~/extra.conf\n{leftover}"));
+        assert_eq!(append_leftover(&once, leftover), None); //imported a second time
+        assert_eq!(append_leftover(&once, ""), None);
+    }
+    #[test]
+    fn generated_config_imports_unchanged() { //everything we write has to be recognised again
+        let mut data = FileData::default();
+        data.primary = ShortcutKey::Mod3;
+        data.secondary = ShortcutKey::Caps;
+        data.binds[0].mods = vec![ShortcutKey::Mod5];
+        data.workspaces.follow = true;
+        data.workspaces.cycle = true;
+        data.workspaces.set_monitor(2, "DP-1".to_string());
+        data.directions.keys = DirectionKeys::Wasd;
+        data.directions.resize = true;
+        data.directions.resize_mod = ShortcutKey::Alt;
+        data.directions.submap = true;
+        data.directions.submap_key = "M".to_string();
+        data.monitors = vec![
+            MonitorCfg {name: "eDP-1".to_string(), width: 2880, height: 1800, refresh: 60.001, x: 0, y: 0, scale: 1.875, transform: Transform::Normal},
+            MonitorCfg {name: "DP-1".to_string(), width: 1920, height: 1080, refresh: 0.0, x: 1536, y: -200, scale: 1.0, transform: Transform::Flipped270},
+        ];
+        data.input.kb_layout = "fr".to_string();
+        data.input.kb_variant = "azerty".to_string();
+        data.input.repeat_rate = 50;
+        data.input.sensitivity = 0.4;
+        data.input.natural_scroll = true;
+        data.input.tap_to_click = false;
+        data.input.follow_mouse = FollowMouse::Never;
+        data.layout.engine = LayoutEngine::Master;
+        data.layout.orientation = MasterOrientation::Right;
+        data.layout.new_is_master = false;
+        data.win_anim = WindowAnimation::PopIn;
+        data.animations.popin = 45;
+        data.animations.beziers.push(Bezier {name: "snappy".to_string(), x0: 0.2, y0: 1.0, x1: 0.3, y1: 1.0});
+        data.animations.entry_mut(AnimationName::Windows).curve = "snappy".to_string();
+        data.animations.entry_mut(AnimationName::WindowsOut).enabled = false;
+        data.animations.entry_mut(AnimationName::Fade).speed = 2.5;
+        data.animations.entry_mut(AnimationName::Layers).enabled = false;
+        data.window_rules = vec![
+            WindowRule {action: RuleAction::Opacity, arg: "0.9 0.8".to_string(), class: "^(kitty)$".to_string(), xwayland: RuleState::No, ..WindowRule::default()},
+            WindowRule {action: RuleAction::Pin, title: "^(Picture-in-Picture)$".to_string(), floating: RuleState::Yes, ..WindowRule::default()},
+        ];
+        let input = RenderInput {
+            data: data.clone(),
+            palette: Palette {
+                background: "000000".to_string(),
+                text: "ffffff".to_string(),
+                primary: "0077ff".to_string(),
+                success: "00ff00".to_string(),
+                danger: "ff0000".to_string(),
+            },
+            home: "/home/test/.config".to_string(),
+            usercfg: None,
+        };
+        let imported = parse_hyprland(&render_hyprland(&input), "/home/test/.config/hypr", FileData::default());
+        assert_eq!(imported.warnings, vec![]);
+        assert_eq!(imported.data.primary, data.primary);
+        assert_eq!(imported.data.secondary, data.secondary);
+        assert_eq!(imported.data.binds, data.binds);
+        assert_eq!(imported.data.autostart, data.autostart);
+        assert_eq!(imported.data.border, data.border);
+        assert_eq!(imported.data.workspaces, data.workspaces);
+        assert_eq!(imported.data.directions, data.directions);
+        assert_eq!(imported.data.monitors, data.monitors);
+        assert_eq!(imported.data.input, data.input);
+        assert_eq!(imported.data.layout, data.layout);
+        assert_eq!(imported.data.window_rules, data.window_rules);
+        assert_eq!((imported.data.win_anim, imported.data.work_anim, imported.data.blur), (data.win_anim, data.work_anim, data.blur));
+        assert_eq!(imported.data.animations, data.animations);
+    }
+}
@@ -0,0 +1,123 @@
+#![allow(dead_code)]
+use std::io::ErrorKind;
+use std::process::Command;
+use serde_derive::Deserialize;
+use crate::lib_cfg::{CfgError, MonitorCfg, Transform};
+
+//The outputs hyprland knows about right now, read from hyprctl monitors -j
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Output { //the fields of hyprctl's json we use, the rest is ignored
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub width: u32,
+    pub height: u32,
+    #[serde(rename = "refreshRate")]
+    pub refresh: f64,
+    pub x: i32,
+    pub y: i32,
+    pub scale: f64,
+    #[serde(default)]
+    pub transform: usize,
+    #[serde(default, rename = "availableModes")]
+    pub modes: Vec<String>, //like 1920x1080@60.00Hz
+}
+
+pub fn parse_outputs(json: &str) -> Result<Vec<Output>, CfgError> {
+    serde_json::from_str(json).map_err(|e| CfgError::BadOutput("hyprctl monitors".to_string(), e.to_string()))
+}
+pub fn read_outputs() -> Result<Vec<Output>, CfgError> { //only works inside a running hyprland session
+    let out = Command::new("hyprctl").args(["monitors", "-j"]).output().map_err(|e| {
+        if e.kind() == ErrorKind::NotFound {
+            CfgError::MissingTool("hyprctl".to_string())
+        } else {
+            CfgError::Io("hyprctl".to_string(), e)
+        }
+    })?;
+    if !out.status.success() {
+        return Err(CfgError::BadOutput("hyprctl monitors".to_string(), String::from_utf8_lossy(&out.stderr).trim().to_string()));
+    }
+    parse_outputs(&String::from_utf8_lossy(&out.stdout))
+}
+pub fn parse_mode(mode: &str) -> Option<(u32, u32, f64)> { //WxH@RHz as hyprctl lists them or WxH@R as monitor= takes them, the rate is optional
+    let mode = mode.trim().trim_end_matches("Hz");
+    let (size, refresh) = match mode.split_once('@') {
+        Some((size, refresh)) => (size, refresh.parse().ok()?),
+        None => (mode, 0.0)
+    };
+    let (width, height) = size.split_once('x')?;
+    Some((width.parse().ok()?, height.parse().ok()?, refresh))
+}
+pub fn mode_matches(monitor: &MonitorCfg, mode: &str) -> bool { //hyprctl rounds the listed rates to two places
+    match parse_mode(mode) {
+        Some((width, height, refresh)) => width == monitor.width && height == monitor.height && (refresh - monitor.refresh).abs() < 0.01,
+        None => false
+    }
+}
+pub fn monitor_from_output(output: &Output) -> MonitorCfg { //keeps the output as it is now
+    MonitorCfg {
+        name: output.name.clone(),
+        width: output.width,
+        height: output.height,
+        refresh: output.refresh,
+        x: output.x,
+        y: output.y,
+        scale: output.scale,
+        transform: Transform::ALL.get(output.transform).copied().unwrap_or_default(),
+    }
+}
+pub fn logical_width(monitor: &MonitorCfg) -> i32 { //what the layout sees, after rotation and scaling
+    let rotated = matches!(monitor.transform, Transform::Rotate90 | Transform::Rotate270 | Transform::Flipped90 | Transform::Flipped270);
+    let width = if rotated {monitor.height} else {monitor.width};
+    if monitor.scale > 0.0 {
+        (width as f64 / monitor.scale).round() as i32
+    } else {
+        width as i32
+    }
+}
+pub fn arrange(monitors: &mut Vec<MonitorCfg>) { //side by side from left to right in list order, top edges lined up
+    let mut x = 0;
+    for monitor in monitors {
+        monitor.x = x;
+        monitor.y = 0;
+        x = x + logical_width(monitor);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hyprctl_output_is_read() {
+        let outputs = parse_outputs(include_str!("../tests/fixtures/hyprctl_monitors.json")).unwrap();
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(outputs[0].name, "eDP-1");
+        assert_eq!(outputs[1].modes[0], "2560x1440@143.91Hz");
+        let mut monitors: Vec<MonitorCfg> = outputs.iter().map(monitor_from_output).collect();
+        assert_eq!(monitors[1], MonitorCfg {
+            name: "DP-1".to_string(),
+            width: 2560,
+            height: 1440,
+            refresh: 143.912,
+            x: 1536,
+            y: 0,
+            scale: 1.0,
+            transform: Transform::Rotate90,
+        });
+        assert!(mode_matches(&monitors[1], &outputs[1].modes[0]));
+        assert!(!mode_matches(&monitors[1], &outputs[1].modes[1]));
+        monitors.swap(0, 1);
+        arrange(&mut monitors);
+        assert_eq!((monitors[0].x, monitors[1].x), (0, 1440)); //rotated, so as wide as it is high
+        assert!(parse_outputs("not json").is_err());
+    }
+    #[test]
+    fn modes_are_parsed() {
+        assert_eq!(parse_mode("1920x1080@60.00Hz"), Some((1920, 1080, 60.0)));
+        assert_eq!(parse_mode("1920x1080@59.951"), Some((1920, 1080, 59.951)));
+        assert_eq!(parse_mode("1280x720"), Some((1280, 720, 0.0)));
+        assert_eq!(parse_mode("preferred"), None);
+    }
+}
@@ -0,0 +1,819 @@
+#![allow(dead_code)]
+use std::collections::BTreeMap;
+use std::fs;
+use std::os::unix::fs::{PermissionsExt, symlink};
+use std::process::Command;
+use toml::to_string;
+use gettextrs::gettext as tr;
+use crate::lib_cfg::*;
+
+//The render layer, turns the contents of cfg.toml into every file we generate without touching the disk
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette { //the active theme's colors as hex strings without the #
+    pub background: String,
+    pub text: String,
+    pub primary: String,
+    pub success: String,
+    pub danger: String,
+}
+#[derive(Debug, Clone)]
+pub struct RenderInput {
+    pub data: FileData,
+    pub palette: Palette,
+    pub home: String, //the config directory every output path is built from, usually get_home()
+    pub usercfg: Option<String>, //usercfg.conf with an import's leftover lines added, None leaves the file alone
+}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PostAction { //things to do once every file is written, in order
+    Touch(String), //make sure a file the user owns exists without changing it
+    MakeExecutable(String),
+    Symlink(String, String), //target, link
+    Run(Vec<String>), //program and its arguments, not waited on
+}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rendered {
+    pub files: BTreeMap<String, String>, //path -> contents
+    pub actions: Vec<PostAction>,
+}
+
+pub fn cfg_path(home: &str) -> String {
+    format!("{home}/Oceania/cfg.toml")
+}
+pub fn hyprland_path(home: &str) -> String {
+    format!("{home}/hypr/hyprland.conf")
+}
+pub fn waybar_path(home: &str) -> String {
+    format!("{home}/waybar/config.jsonc")
+}
+pub fn waybar_style_path(home: &str) -> String {
+    format!("{home}/waybar/style.css")
+}
+pub fn usercfg_path(home: &str) -> String { //the user's own additions, sourced last so they win
+    format!("{home}/hypr/usercfg.conf")
+}
+pub fn render(input: &RenderInput) -> Result<Rendered, CfgError> {
+    let home = &input.home;
+    let mut files = BTreeMap::new();
+    files.insert(cfg_path(home), render_cfg(&input.data)?);
+    if check_profile_name(&input.data.profile).is_ok() { //saving a profile keeps its copy up to date too
+        files.insert(profile_path(home, &input.data.profile), render_cfg(&input.data)?);
+    }
+    files.insert(hyprland_path(home), render_hyprland(input));
+    files.insert(waybar_path(home), render_waybar(input));
+    files.insert(waybar_style_path(home), render_waybar_style(input));
+    if let Some(usercfg) = &input.usercfg { //written and backed up with the rest, so a cancelled import leaves no trace
+        files.insert(usercfg_path(home), usercfg.clone());
+    }
+    let mut actions = vec![
+        PostAction::Touch(usercfg_path(home)),
+        PostAction::Touch(format!("{home}/hypr/autostart")),
+        PostAction::MakeExecutable(format!("{home}/hypr/autostart")),
+        PostAction::Run(vec!["hyprctl".to_string(), "reload".to_string()]),
+        PostAction::Run(vec!["pkill".to_string(), "-SIGUSR2".to_string(), "waybar".to_string()]), //waybar reloads its config and style when sent SIGUSR2
+    ];
+    if input.data.wallpaper.len() > 0 { //no wallpaper picked yet
+        actions.push(PostAction::Symlink(input.data.wallpaper.clone(), format!("{home}/Oceania/wallpaper")));
+        actions.push(PostAction::Run(vec!["swww".to_string(), "img".to_string(), format!("{home}/Oceania/wallpaper")]));
+    }
+    Ok(Rendered {
+        files,
+        actions
+    })
+}
+pub fn render_cfg(data: &FileData) -> Result<String, CfgError> {
+    to_string(data).map_err(CfgError::Serialize)
+}
+pub fn render_hyprland(input: &RenderInput) -> String {
+    let data = &input.data;
+    let home = &input.home;
+    let primary_key = Some(data.primary);
+    let secondary_key = Some(data.secondary);
+    let pri_k = rip_shortcut(primary_key);
+    let sec_k = rip_shortcut(secondary_key);
+    let binds = rip_keybinds(&data.binds, primary_key, secondary_key);
+    let gaps = data.border.gaps;
+    let width = data.border.width;
+    let radius = data.border.radius;
+    let animations = rip_animations(&data.animations, data.win_anim, data.work_anim);
+    let blur = data.blur;
+    let autostart = rip_autostart(&data.autostart);
+    let monitors = rip_monitors(&data.monitors);
+    let input_block = rip_input(&data.input);
+    let layout = encode(&data.layout.engine);
+    let layout_block = rip_layout(&data.layout);
+    let layout_binds = rip_layout_binds(&data.layout);
+    let window_rules = rip_window_rules(&data.window_rules);
+    let directions = rip_directions(&data.directions, primary_key);
+    let workspaces = rip_workspaces(&data.workspaces);
+    let active_border = &input.palette.primary;
+    let sector_head = r#"{"#;
+    let sector_tail = r#"}"#;
+    format!("#AUTO-GENERATED CONFIG, DO NOT EDIT, CHANGES WILL BE OVERWRITTEN \n \
+    $mainMod = {pri_k}\n \
+    $secMod = {sec_k}\n\
+    {monitors} \
+    exec-once=oceania-shell\n \
+    exec-once={home}/hypr/autostart\n\
+    {autostart}{binds}{directions}{workspaces}{layout_binds}{window_rules} \
+    general {sector_head}\n \
+    gaps_in = {gaps}\n \
+    gaps_out = {gaps}\n \
+    border_size = {width}\n \
+    layout = {layout}\n \
+    col.active_border = rgb({active_border})
+    {sector_tail}\n\
+    {layout_block} \
+    decoration {sector_head}\n \
+    rounding = {radius}\n \
+    blur {sector_head} \n \
+    enabled={blur} \n \
+    size=3 \n \
+    passes=3 \n \
+    new_optimizations=true \n \
+    {sector_tail}
+    {sector_tail}\n\
+    {input_block} \
+    animations {sector_head}\n \
+    enabled = true\n\
+    {animations} \
+    {sector_tail}\n \
+    source={home}/hypr/usercfg.conf
+    ")
+}
+pub fn render_waybar(input: &RenderInput) -> String {
+    let data = &input.data;
+    let left_widgets = rip_widget_vec(data.widgets_left.clone());
+    let center_widgets = rip_widget_vec(data.widgets_center.clone());
+    let right_widgets = rip_widget_vec(data.widgets_right.clone());
+    let mut widget_cfgs = vec![];
+    for widget in BarWidget::ALL {
+        widget_cfgs.push(rip_widget_cfg(widget));
+    }
+    let widget_cfgs = widget_cfgs.join(",\n");
+    format!("// AUTO-GENERATED CONFIG, DO NOT EDIT, CHANGES WILL BE OVERWRITTEN
+{{
+    \"layer\": \"top\",
+    \"position\": \"top\",
+    \"modules-left\": {left_widgets},
+    \"modules-center\": {center_widgets},
+    \"modules-right\": {right_widgets},
+{widget_cfgs}
+}}
+")
+}
+pub fn render_waybar_style(input: &RenderInput) -> String {
+    let background = &input.palette.background;
+    let text = &input.palette.text;
+    let primary = &input.palette.primary;
+    let success = &input.palette.success;
+    let danger = &input.palette.danger;
+    let radius = input.data.border.radius;
+    let gaps = input.data.border.gaps;
+    format!("/* AUTO-GENERATED STYLE, DO NOT EDIT, CHANGES WILL BE OVERWRITTEN */
+* {{
+    font-family: sans-serif;
+    font-size: 14px;
+    border: none;
+    border-radius: 0;
+    min-height: 0;
+}}
+window#waybar {{
+    background: transparent;
+    color: #{text};
+}}
+.modules-left,
+.modules-center,
+.modules-right {{
+    background: #{background};
+    border-radius: {radius}px;
+    margin-top: {gaps}px;
+    padding: 0 {radius}px;
+}}
+.modules-left {{
+    margin-left: {gaps}px;
+}}
+.modules-right {{
+    margin-right: {gaps}px;
+}}
+.modules-left > widget > *,
+.modules-center > widget > *,
+.modules-right > widget > * {{
+    padding: 0 8px;
+    color: #{text};
+}}
+#workspaces button {{
+    color: #{text};
+    border-radius: {radius}px;
+}}
+#workspaces button.active,
+#taskbar button.active {{
+    background: #{primary};
+    color: #{background};
+}}
+#battery.charging,
+#bluetooth.connected {{
+    color: #{success};
+}}
+#battery.warning,
+#battery.critical,
+#temperature.critical,
+#network.disconnected,
+#pulseaudio.muted {{
+    color: #{danger};
+}}
+")
+}
+pub fn write_rendered(out: &Rendered) -> Vec<CfgError> { //the only part of rendering with side effects, an empty list means everything worked
+    write_rendered_with(out, None)
+}
+pub fn write_rendered_with(out: &Rendered, tool_dir: Option<&str>) -> Vec<CfgError> { //tool_dir is where the programs we run are looked up, None searches PATH
+    for path in out.files.keys() {
+        if let Some(parent) = std::path::Path::new(path).parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                return vec![CfgError::Io(parent.display().to_string(), e)];
+            }
+        }
+    }
+    if let Err(e) = write_all_atomic(&out.files) { //hyprland and waybar always come from the same save
+        return vec![e]; //the actions below all expect the files to be in place
+    }
+    run_actions_with(&out.actions, tool_dir)
+}
+const SECTIONS: [&str; 19] = ["general", "decoration", "blur", "shadow", "animations", "input", "touchpad", "touchdevice", "tablet", "gestures", "group", "groupbar", "misc", "binds", "xwayland", "dwindle", "master", "cursor", "debug"];
+const KEYWORDS: [&str; 12] = ["exec", "exec-once", "source", "monitor", "env", "workspace", "windowrule", "windowrulev2", "layerrule", "bezier", "animation", "submap"];
+const BIND_FLAGS: &str = "lrenmti"; //bind, binde, bindl and so on
+pub fn check_hyprland(path: &str, contents: &str) -> Vec<CfgError> { //catches a broken hyprland.conf before it replaces a working one
+    let mut errors = vec![];
+    let mut open: Vec<(usize, String)> = vec![]; //the sections we are inside of, with the line each started on
+    let mods: Vec<String> = ShortcutKey::ALL.iter().map(|x| rip_shortcut(Some(*x))).collect();
+    let dispatchers: Vec<String> = Dispatcher::ALL.iter().map(|x| rip_dispatcher(*x)).collect();
+    let lines: Vec<&str> = contents.lines().collect();
+    for i in 0..lines.len() {
+        let line = lines[i].trim();
+        let mut problem = None;
+        if line.len() == 0 || line.starts_with('#') {
+            continue;
+        } else if line == "}" {
+            if open.pop().is_none() {
+                problem = Some(tr("closes a section that was never opened"));
+            }
+        } else if let Some(name) = line.strip_suffix('{') {
+            let name = name.trim();
+            if !SECTIONS.contains(&name) {
+                problem = Some(format!("{}: {name}", tr("unknown section")));
+            }
+            open.push((i + 1, name.to_string()));
+        } else if let Some((key, value)) = line.split_once('=') {
+            let key = key.trim();
+            let bind_flags = key.strip_prefix("bind").filter(|x| x.chars().all(|c| BIND_FLAGS.contains(c)));
+            if key.starts_with('$') || open.len() > 0 && key.len() > 0 && key.chars().all(|c| c.is_ascii_alphanumeric() || "_.:-".contains(c)) {
+                //variables, and options inside a section, hyprland knows far too many of those to list
+            } else if let Some(flags) = bind_flags {
+                problem = check_bind(value, flags.contains('m'), &mods, &dispatchers);
+            } else if !KEYWORDS.contains(&key) && !key.split_once(':').is_some_and(|(x, _)| SECTIONS.contains(&x)) {
+                problem = Some(format!("{}: {key}", tr("unknown keyword")));
+            }
+        } else {
+            problem = Some(tr("expected keyword = value"));
+        }
+        if let Some(message) = problem {
+            errors.push(CfgError::Invalid(path.to_string(), i + 1, message));
+        }
+    }
+    for (line, name) in open {
+        errors.push(CfgError::Invalid(path.to_string(), line, format!("{}: {name}", tr("section is never closed"))));
+    }
+    errors
+}
+fn check_bind(value: &str, mouse: bool, mods: &[String], dispatchers: &[String]) -> Option<String> { //MODS, key, dispatcher and an optional argument
+    let parts: Vec<&str> = value.splitn(4, ',').map(|x| x.trim()).collect();
+    if parts.len() < 3 {
+        return Some(tr("a bind needs modifiers, a key and a dispatcher"));
+    }
+    for modifier in parts[0].split(|c: char| c == '_' || c.is_whitespace()) {
+        if modifier.len() > 0 && !modifier.starts_with('$') && modifier != "CTRL" && !mods.iter().any(|x| x == modifier) {
+            return Some(format!("{}: {modifier}", tr("unknown modifier")));
+        }
+    }
+    if parts[1].len() == 0 {
+        return Some(tr("a bind has no key, pick one on the keybindings page"));
+    }
+    if !mouse && !parts[2].starts_with('$') && !dispatchers.iter().any(|x| x == parts[2]) { //mouse binds take movewindow and resizewindow without the dispatcher list
+        return Some(format!("{}: {}", tr("unknown dispatcher"), parts[2]));
+    }
+    None
+}
+pub fn write_all_atomic(files: &BTreeMap<String, String>) -> Result<(), CfgError> { //every file is staged before any is replaced, a failed rename puts back the ones already done
+    let mut staged: Vec<(String, String, Option<String>)> = vec![]; //temp file, target and what the target held before
+    for (path, contents) in files {
+        let target = resolve_link(path);
+        let old = match fs::read_to_string(&target) {
+            Ok(old) => Some(old),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => {
+                discard_staged(&staged);
+                return Err(CfgError::Io(path.clone(), e));
+            }
+        };
+        match stage_file(&target, contents) {
+            Ok(temp) => staged.push((temp, target, old)),
+            Err(e) => {
+                discard_staged(&staged);
+                return Err(e);
+            }
+        }
+    }
+    for i in 0..staged.len() {
+        if let Err(e) = fs::rename(&staged[i].0, &staged[i].1) {
+            discard_staged(&staged[i..]);
+            for j in 0..i { //best effort, the error below is what the user sees
+                let _ = match &staged[j].2 {
+                    Some(old) => write_atomic(&staged[j].1, old),
+                    None => fs::remove_file(&staged[j].1).map_err(|e| CfgError::Io(staged[j].1.clone(), e))
+                };
+            }
+            return Err(CfgError::Io(staged[i].1.clone(), e));
+        }
+    }
+    for (_, target, _) in &staged { //the renames only survive a crash once their directories are on disk
+        sync_dir(target)?;
+    }
+    Ok(())
+}
+fn discard_staged(staged: &[(String, String, Option<String>)]) {
+    for (temp, _, _) in staged {
+        let _ = fs::remove_file(temp);
+    }
+}
+pub fn run_actions(actions: &[PostAction]) -> Vec<CfgError> {
+    run_actions_with(actions, None)
+}
+pub fn run_actions_with(actions: &[PostAction], tool_dir: Option<&str>) -> Vec<CfgError> {
+    let mut errors = vec![];
+    for action in actions {
+        let result = match action {
+            PostAction::Touch(path) => {
+                fs::OpenOptions::new().create(true).append(true).open(path).map(|_| ()).map_err(|e| CfgError::Io(path.clone(), e))
+            }
+            PostAction::MakeExecutable(path) => {
+                fs::metadata(path).and_then(|x| {
+                    let mut perms = x.permissions();
+                    perms.set_mode(perms.mode() | 0o111);
+                    fs::set_permissions(path, perms)
+                }).map_err(|e| CfgError::Io(path.clone(), e))
+            }
+            PostAction::Symlink(target, link) => {
+                let _ = fs::remove_file(link); //ln -sf
+                symlink(target, link).map_err(|e| CfgError::Io(link.clone(), e))
+            }
+            PostAction::Run(argv) => {
+                let program = match tool_dir {
+                    Some(dir) => format!("{dir}/{}", argv[0]),
+                    None => argv[0].clone()
+                };
+                match Command::new(&program).args(&argv[1..]).spawn() {
+                    Ok(..) => Ok(()),
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(CfgError::MissingTool(argv[0].clone())),
+                    Err(e) => Err(CfgError::Io(argv[0].clone(), e))
+                }
+            }
+        };
+        if let Err(e) = result {
+            errors.push(e);
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests { //golden files live in tests/golden, run with UPDATE_GOLDEN=1 to regenerate them after an intended change
+    use super::*;
+    use std::env;
+    use std::path::PathBuf;
+    use std::time::{Duration, Instant};
+
+    const BASE: &str = r#"
+    theme = "light"
+    primary = "super"
+    secondary = "shift"
+    wallpaper = ""
+    win_anim = "popin"
+    work_anim = "slidev"
+    blur = "y"
+    widgets_left = ["CPU", "RAM"]
+    widgets_center = ["Workspaces", "Clock"]
+    widgets_right = ["Audio", "System Tray"]
+    autostart = [{ command = "waybar", enabled = true }, { command = "nm-applet", enabled = false }]
+
+    [border]
+    width = 5
+    radius = 15
+    gaps = 10
+
+    [[binds]]
+    header = "pri"
+    key = "Tab"
+    dispatcher = "exec"
+    arg = "rofi -show drun"
+
+    [[binds]]
+    header = "sec"
+    key = "F"
+    dispatcher = "fullscreen"
+
+    [[binds]]
+    header = "both"
+    key = "Q"
+    dispatcher = "killactive"
+
+    [[binds]]
+    header = "none"
+    key = "Print"
+    dispatcher = "exec"
+    arg = "grim"
+
+    [[binds]]
+    header = "pri"
+    mods = ["alt"]
+    key = "Key1"
+    dispatcher = "movetoworkspacesilent"
+    arg = "1""#;
+    const BORDERS: [(&str, Border); 3] = [
+        ("zero", Border {width: 0, radius: 0, gaps: 0}),
+        ("default", Border {width: 5, radius: 15, gaps: 10}),
+        ("wide", Border {width: 12, radius: 40, gaps: 32}),
+    ];
+
+    fn palette() -> Palette {
+        Palette {
+            background: "E0F5FF".to_string(),
+            text: "001936".to_string(),
+            primary: "0077FF".to_string(),
+            success: "00CB40".to_string(),
+            danger: "FF4C00".to_string(),
+        }
+    }
+    fn input(pri: ShortcutKey, sec: ShortcutKey, win: WindowAnimation, work: WorkAnimation, blur: bool, border: Border) -> RenderInput {
+        let (mut data, _) = parse_cfg_data(BASE).unwrap();
+        data.primary = pri;
+        data.secondary = sec;
+        data.win_anim = win;
+        data.work_anim = work;
+        data.blur = blur;
+        data.border = border;
+        RenderInput {
+            data,
+            palette: palette(),
+            home: "/home/test/.config".to_string(),
+            usercfg: None,
+        }
+    }
+    fn default_input() -> RenderInput {
+        input(ShortcutKey::Super, ShortcutKey::Shift, WindowAnimation::PopIn, WorkAnimation::SlideVert, true, BORDERS[1].1)
+    }
+    fn check_golden(name: &str, output: &str) {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden/hyprland").join(format!("{name}.conf"));
+        if env::var("UPDATE_GOLDEN").is_ok() {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, output).unwrap();
+            return;
+        }
+        let expected = fs::read_to_string(&path).unwrap_or_else(|_| panic!("missing golden file {}, run with UPDATE_GOLDEN=1 to create it", path.display()));
+        assert!(output == expected, "{name}.conf differs from its golden file\n--- expected\n{expected}\n--- got\n{output}");
+    }
+
+    #[test]
+    fn golden_shortcut_keys() { //every bind header appears in BASE, the keys only show up in the $mainMod and $secMod lines so one partner each is enough
+        for pri in ShortcutKey::ALL {
+            for sec in ShortcutKey::ALL {
+                if sec != ShortcutKey::Shift && sec != pri { //and each key with itself, where both headers name the same modifier
+                    continue;
+                }
+                let input = input(pri, sec, WindowAnimation::PopIn, WorkAnimation::SlideVert, true, BORDERS[1].1);
+                check_golden(&format!("keys_{}_{}", encode(&pri), encode(&sec)), &render_hyprland(&input));
+            }
+        }
+    }
+    #[test]
+    fn golden_animations() {
+        for win in WindowAnimation::ALL {
+            for work in WorkAnimation::ALL {
+                let input = input(ShortcutKey::Super, ShortcutKey::Shift, win, work, true, BORDERS[1].1);
+                check_golden(&format!("anim_{}_{}", encode(&win), encode(&work)), &render_hyprland(&input));
+            }
+        }
+    }
+    #[test]
+    fn golden_custom_animations() {
+        let mut input = input(ShortcutKey::Super, ShortcutKey::Shift, WindowAnimation::PopIn, WorkAnimation::Fade, true, BORDERS[1].1);
+        let animations = &mut input.data.animations;
+        animations.popin = 60;
+        animations.beziers.push(Bezier {name: "overshot".to_string(), x0: 0.05, y0: 0.9, x1: 0.1, y1: 1.1});
+        animations.beziers.push(Bezier {name: String::new(), x0: 0.0, y0: 0.0, x1: 1.0, y1: 1.0}); //not named yet, left out
+        animations.beziers.push(Bezier {name: "overshot ".to_string(), x0: 0.1, y0: 0.1, x1: 0.1, y1: 0.1}); //a repeat, left out
+        animations.beziers.push(Bezier {name: "default".to_string(), x0: 0.1, y0: 0.1, x1: 0.1, y1: 0.1}); //would override hyprland's own
+        animations.entry_mut(AnimationName::Windows).curve = "overshot".to_string();
+        animations.entry_mut(AnimationName::Windows).speed = 4.5;
+        animations.entry_mut(AnimationName::WindowsOut).enabled = false;
+        animations.entry_mut(AnimationName::Border).curve = "gone".to_string(); //deleted since, falls back to default
+        animations.entry_mut(AnimationName::Layers).enabled = false;
+        let output = render_hyprland(&input);
+        assert!(check_hyprland("hyprland.conf", &output).is_empty());
+        assert!(output.contains("\n bezier = overshot, 0.05, 0.9, 0.1, 1.1\n animation = windows,1,4.5,overshot,popin 60%\n"));
+        assert!(output.contains("\n animation = windowsOut,0,3,default\n"));
+        assert!(output.contains("\n animation = border,1,10,default\n"));
+        assert_eq!(output.matches("bezier =").count(), 1);
+        check_golden("anim_custom", &output);
+    }
+    #[test]
+    fn golden_blur() {
+        for blur in [true, false] {
+            let input = input(ShortcutKey::Super, ShortcutKey::Shift, WindowAnimation::PopIn, WorkAnimation::SlideVert, blur, BORDERS[1].1);
+            check_golden(&format!("blur_{}", if blur {"y"} else {"n"}), &render_hyprland(&input));
+        }
+    }
+    #[test]
+    fn golden_borders() {
+        for (name, border) in BORDERS {
+            let input = input(ShortcutKey::Super, ShortcutKey::Shift, WindowAnimation::PopIn, WorkAnimation::SlideVert, true, border);
+            check_golden(&format!("border_{name}"), &render_hyprland(&input));
+        }
+    }
+    #[test]
+    fn golden_workspaces() {
+        for keys in WorkspaceKeys::ALL {
+            let mut input = input(ShortcutKey::Super, ShortcutKey::Shift, WindowAnimation::PopIn, WorkAnimation::SlideVert, true, BORDERS[1].1);
+            input.data.workspaces.keys = keys;
+            input.data.workspaces.count = 12; //more than the number row has
+            input.data.workspaces.follow = true;
+            input.data.workspaces.cycle = true;
+            input.data.workspaces.set_monitor(1, "DP-1".to_string());
+            input.data.workspaces.set_monitor(11, "HDMI-A-1".to_string()); //only bound when the row reaches it
+            let output = render_hyprland(&input);
+            assert!(check_hyprland("hyprland.conf", &output).is_empty());
+            check_golden(&format!("workspaces_{}", encode(&keys)), &output);
+        }
+    }
+    #[test]
+    fn golden_directions() {
+        for keys in DirectionKeys::ALL {
+            let mut input = input(ShortcutKey::Super, ShortcutKey::Shift, WindowAnimation::PopIn, WorkAnimation::SlideVert, true, BORDERS[1].1);
+            input.data.directions.keys = keys;
+            input.data.directions.resize = true;
+            input.data.directions.resize_mod = ShortcutKey::Alt;
+            input.data.directions.submap = true;
+            let output = render_hyprland(&input);
+            assert!(check_hyprland("hyprland.conf", &output).is_empty());
+            check_golden(&format!("directions_{}", encode(&keys)), &output);
+        }
+        for (resize_mod, clash) in [(ShortcutKey::Super, BuiltInBind::Focus), (ShortcutKey::Shift, BuiltInBind::Move)] { //the primary or secondary key alone would fire both
+            let mut input = default_input();
+            input.data.directions.resize = true;
+            input.data.directions.resize_mod = resize_mod;
+            let data = &input.data;
+            let conflicts = find_conflicts(&data.binds, Some(data.primary), Some(data.secondary), &data.workspaces, &data.directions, &data.layout);
+            assert_eq!(conflicts, vec![BindConflict {index: None, other: None, built_in: vec![clash, BuiltInBind::Resize], suggestion: None}]);
+        }
+        let mut input = default_input();
+        input.data.directions.keys = DirectionKeys::Vim;
+        input.data.directions.submap = true;
+        input.data.directions.submap_key = "L".to_string(); //already moves focus right
+        let data = &input.data;
+        let conflicts = find_conflicts(&data.binds, Some(data.primary), Some(data.secondary), &data.workspaces, &data.directions, &data.layout);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].built_in, vec![BuiltInBind::Focus, BuiltInBind::ResizeMode]);
+    }
+    #[test]
+    fn golden_monitors() {
+        let mut input = input(ShortcutKey::Super, ShortcutKey::Shift, WindowAnimation::PopIn, WorkAnimation::SlideVert, true, BORDERS[1].1);
+        input.data.monitors = vec![
+            MonitorCfg {name: "eDP-1".to_string(), width: 2880, height: 1800, refresh: 60.001, x: 0, y: 0, scale: 1.875, transform: Transform::Normal},
+            MonitorCfg {name: "DP-1".to_string(), width: 2560, height: 1440, refresh: 143.912, x: 1536, y: 0, scale: 1.0, transform: Transform::Rotate90},
+            MonitorCfg {name: "HDMI-A-1".to_string(), width: 1920, height: 1080, refresh: 0.0, x: -1920, y: 0, scale: 1.0, transform: Transform::Normal},
+        ];
+        let output = render_hyprland(&input);
+        assert!(check_hyprland("hyprland.conf", &output).is_empty());
+        check_golden("monitors", &output);
+    }
+    #[test]
+    fn golden_input() {
+        let mut input = input(ShortcutKey::Super, ShortcutKey::Shift, WindowAnimation::PopIn, WorkAnimation::SlideVert, true, BORDERS[1].1);
+        input.data.input = Input {
+            kb_layout: "de".to_string(),
+            kb_variant: "nodeadkeys".to_string(),
+            repeat_rate: 40,
+            repeat_delay: 250,
+            sensitivity: -0.3,
+            natural_scroll: false,
+            touchpad_natural_scroll: true,
+            tap_to_click: false,
+            follow_mouse: FollowMouse::Click,
+        };
+        let output = render_hyprland(&input);
+        assert!(check_hyprland("hyprland.conf", &output).is_empty());
+        check_golden("input", &output);
+    }
+    #[test]
+    fn golden_window_rules() {
+        let mut input = input(ShortcutKey::Super, ShortcutKey::Shift, WindowAnimation::PopIn, WorkAnimation::SlideVert, true, BORDERS[1].1);
+        let rule = |action, arg: &str, class: &str, title: &str, floating, xwayland| WindowRule {
+            action,
+            arg: arg.to_string(),
+            class: class.to_string(),
+            title: title.to_string(),
+            floating,
+            xwayland,
+        };
+        input.data.window_rules = vec![
+            rule(RuleAction::Float, "", "^(pavucontrol)$", "", RuleState::Any, RuleState::Any),
+            rule(RuleAction::Workspace, "2", "^(firefox)$", "", RuleState::Any, RuleState::Any),
+            rule(RuleAction::Size, "800 600", "", "^(Picture-in-Picture)$", RuleState::Yes, RuleState::Any),
+            rule(RuleAction::Move, "100 100", "", "^(Picture-in-Picture)$", RuleState::Any, RuleState::Any),
+            rule(RuleAction::Opacity, "0.9", "^(kitty)$", "", RuleState::Any, RuleState::No),
+            rule(RuleAction::Pin, "", "", "", RuleState::Yes, RuleState::Yes),
+            rule(RuleAction::Fullscreen, "", "^(steam_app_.*)$", "", RuleState::Any, RuleState::Any),
+            rule(RuleAction::IdleInhibit, "fullscreen", "^(mpv)$", "", RuleState::Any, RuleState::Any),
+            rule(RuleAction::Float, "", "", "", RuleState::Any, RuleState::Any), //nothing to match yet, left out
+            rule(RuleAction::Workspace, " ", "^(discord)$", "", RuleState::Any, RuleState::Any), //no workspace yet, left out
+        ];
+        let output = render_hyprland(&input);
+        assert!(check_hyprland("hyprland.conf", &output).is_empty());
+        assert_eq!(output.matches("windowrulev2").count(), 8);
+        check_golden("window_rules", &output);
+    }
+    #[test]
+    fn golden_layouts() {
+        for engine in LayoutEngine::ALL {
+            let mut input = input(ShortcutKey::Super, ShortcutKey::Shift, WindowAnimation::PopIn, WorkAnimation::SlideVert, true, BORDERS[1].1);
+            input.data.layout.engine = engine;
+            input.data.layout.orientation = MasterOrientation::Center;
+            let output = render_hyprland(&input);
+            assert!(check_hyprland("hyprland.conf", &output).is_empty());
+            check_golden(&format!("layout_{}", encode(&engine)), &output);
+        }
+    }
+    #[test]
+    fn every_combination_is_well_formed() { //too many to keep golden files for, so check the shape of each instead
+        for pri in ShortcutKey::ALL {
+            for sec in ShortcutKey::ALL {
+                for win in WindowAnimation::ALL {
+                    for work in WorkAnimation::ALL {
+                        for blur in [true, false] {
+                            for (_, border) in BORDERS {
+                                let output = render_hyprland(&input(pri, sec, win, work, blur, border));
+                                let lines: Vec<&str> = output.lines().collect();
+                                assert!(lines[0].starts_with("#AUTO-GENERATED CONFIG"));
+                                assert!(check_hyprland("hyprland.conf", &output).is_empty());
+                                assert_eq!(output.matches('{').count(), output.matches('}').count());
+                                for line in &lines[1..] {
+                                    assert!(line.starts_with(' '), "unindented line {line:?}");
+                                    assert!(!line.contains("{}"), "empty section in {line:?}");
+                                    if let Some(i) = line.find('{') {
+                                        assert!(line[i + 1..].trim().is_empty(), "leftover placeholder in {line:?}");
+                                    }
+                                }
+                                let pri_k = rip_shortcut(Some(pri));
+                                let sec_k = rip_shortcut(Some(sec));
+                                assert!(output.contains(&format!("\n $mainMod = {pri_k}\n $secMod = {sec_k}\n")));
+                                assert!(output.contains("\n bind = $mainMod,1, workspace, 1 \n"));
+                                assert!(output.contains("\n bind = $mainMod $secMod,0,movetoworkspacesilent,10 \n"));
+                                assert!(output.contains(&format!("\n gaps_in = {}\n", border.gaps)));
+                                assert!(output.contains(&format!("\n border_size = {}\n", border.width)));
+                                assert!(output.contains(&format!("\n rounding = {}\n", border.radius)));
+                                assert!(output.contains(&format!("\n enabled={blur} \n")));
+                                match rip_win_anim(win, 80) {
+                                    Some(style) => assert!(output.contains(&format!("\n animation = windows,1,3,default,{style}\n"))),
+                                    None => assert!(output.contains("\n animation = windows,0,3,default\n"))
+                                }
+                                match rip_work_anim(work) {
+                                    Some(style) => assert!(output.contains(&format!("\n animation = workspaces,1,3,default,{style}\n"))),
+                                    None => assert!(output.contains("\n animation = workspaces,0,3,default\n"))
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    #[test]
+    fn broken_configs_are_caught() {
+        let lines = |contents: &str| -> Vec<usize> {
+            check_hyprland("hyprland.conf", contents).iter().map(|x| match x {
+                CfgError::Invalid(_, line, _) => *line,
+                _ => 0
+            }).collect()
+        };
+        assert_eq!(lines(" general {\n gaps_in = 5\n }\n bind=SUPER,Q,killactive\n $mod = SUPER\n bindm=$mod,mouse:272,movewindow\n"), Vec::<usize>::new());
+        assert_eq!(lines(" general {\n gaps_in = 5\n"), vec![1]); //never closed
+        assert_eq!(lines(" }\n"), vec![1]);
+        assert_eq!(lines(" generl {\n }\n"), vec![1]);
+        assert_eq!(lines(" gaps_in = 5\n"), vec![1]); //options belong in a section
+        assert_eq!(lines(" exec-once=waybar\n bind=SUPER,,exec,foot\n bind=SUPER,Q\n bind=HYPER,Q,exec\n bind=SUPER,Q,explode\n"), vec![2, 3, 4, 5]);
+        assert_eq!(lines(" just some text\n"), vec![1]);
+    }
+    #[test]
+    fn render_touches_nothing() {
+        let out = render(&default_input()).unwrap();
+        assert_eq!(out.files.len(), 4);
+        assert!(!PathBuf::from("/home/test").exists());
+        assert_eq!(out.files[&hyprland_path("/home/test/.config")], render_hyprland(&default_input()));
+        assert_eq!(parse_cfg_data(&out.files[&cfg_path("/home/test/.config")]).unwrap().0.binds.len(), 5);
+    }
+    #[test]
+    fn usercfg_is_only_written_after_an_import() {
+        let mut input = default_input();
+        assert!(!render(&input).unwrap().files.contains_key(&usercfg_path("/home/test/.config")));
+        input.usercfg = Some("bind = $mainMod, F, fakefullscreen\n".to_string());
+        assert_eq!(render(&input).unwrap().files[&usercfg_path("/home/test/.config")], "bind = $mainMod, F, fakefullscreen\n");
+    }
+    #[test]
+    fn active_profile_is_saved_too() {
+        let mut input = default_input();
+        input.data.profile = "docked".to_string();
+        let out = render(&input).unwrap();
+        assert_eq!(out.files[&profile_path("/home/test/.config", "docked")], out.files[&cfg_path("/home/test/.config")]);
+        input.data.profile = "../hypr/hyprland".to_string(); //never lets a name point outside the profile directory
+        assert_eq!(render(&input).unwrap().files.len(), 4);
+    }
+    #[test]
+    fn missing_tools_are_reported() {
+        let out = Rendered {
+            files: BTreeMap::new(),
+            actions: vec![PostAction::Run(vec!["cuttlefish-no-such-tool".to_string()])],
+        };
+        let errors = write_rendered(&out);
+        assert!(matches!(&errors[..], [CfgError::MissingTool(name)] if name == "cuttlefish-no-such-tool"));
+    }
+    #[test]
+    fn symlinked_configs_stay_linked() { //stow and similar keep the real file elsewhere
+        let root = env::temp_dir().join(format!("cuttlefish-link-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("dotfiles")).unwrap();
+        let real = root.join("dotfiles/hyprland.conf");
+        let link = root.join("hyprland.conf");
+        fs::write(&real, "old").unwrap();
+        symlink(&real, &link).unwrap();
+        let mut files = BTreeMap::new();
+        files.insert(link.display().to_string(), "new".to_string());
+        write_all_atomic(&files).unwrap();
+        assert!(fs::symlink_metadata(&link).unwrap().file_type().is_symlink());
+        assert_eq!(fs::read_to_string(&real).unwrap(), "new");
+        write_atomic(&link.display().to_string(), "newer").unwrap();
+        assert!(fs::symlink_metadata(&link).unwrap().file_type().is_symlink());
+        assert_eq!(fs::read_to_string(&real).unwrap(), "newer");
+        let _ = fs::remove_dir_all(&root);
+    }
+    #[test]
+    fn load_and_apply_in_temp_home() { //the config directory and the stubbed hyprctl, pkill and swww are passed in, tests run in parallel and share the environment
+        let root = env::temp_dir().join(format!("cuttlefish-test-{}", std::process::id()));
+        let bin = root.join("bin");
+        let log = root.join("commands.log");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&bin).unwrap();
+        for program in ["hyprctl", "pkill", "swww"] {
+            let stub = bin.join(program);
+            fs::write(&stub, format!("#!/bin/sh\necho \"{program} $*\" >> {}\n", log.display())).unwrap();
+            fs::set_permissions(&stub, fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        let wallpaper = root.join("wallpaper.png");
+        fs::write(&wallpaper, "").unwrap();
+        let home = root.join("config").display().to_string();
+
+        let cfg = cfg_path(&home);
+        let old_cfg = include_str!("../tests/fixtures/cfg_v1.toml");
+        fs::create_dir_all(PathBuf::from(&cfg).parent().unwrap()).unwrap();
+        fs::write(&cfg, old_cfg).unwrap();
+        let (data, warnings) = get_cfg_data_in(&home).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].key, "version");
+        assert_eq!(fs::read_to_string(format!("{cfg}.v1.bak")).unwrap(), old_cfg);
+        assert_eq!(fs::read_to_string(&cfg).unwrap(), render_cfg(&data).unwrap());
+        assert_eq!(get_cfg_data_in(&home).unwrap().1, vec![]); //migrated once, not on every start
+
+        let mut input = default_input();
+        input.home = home.clone();
+        input.data.wallpaper = wallpaper.display().to_string();
+        let out = render(&input).unwrap();
+        assert!(write_rendered_with(&out, Some(&bin.display().to_string())).is_empty());
+
+        for (path, contents) in &out.files {
+            assert_eq!(&fs::read_to_string(path).unwrap(), contents);
+        }
+        assert!(PathBuf::from(format!("{home}/hypr/usercfg.conf")).exists());
+        assert!(fs::metadata(format!("{home}/hypr/autostart")).unwrap().permissions().mode() & 0o111 != 0);
+        assert_eq!(fs::read_link(format!("{home}/Oceania/wallpaper")).unwrap(), wallpaper);
+
+        let started = Instant::now(); //the commands are spawned, not waited on
+        let mut commands = String::new();
+        while commands.lines().count() < 3 && started.elapsed() < Duration::from_secs(5) {
+            std::thread::sleep(Duration::from_millis(20));
+            commands = fs::read_to_string(&log).unwrap_or_default();
+        }
+        assert!(commands.contains("hyprctl reload\n"));
+        assert!(commands.contains("pkill -SIGUSR2 waybar\n"));
+        assert!(commands.contains(&format!("swww img {home}/Oceania/wallpaper\n")));
+        let _ = fs::remove_dir_all(&root);
+    }
+}
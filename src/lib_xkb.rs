@@ -0,0 +1,97 @@
+#![allow(dead_code)]
+use std::fs;
+use std::io::ErrorKind;
+use crate::lib_cfg::CfgError;
+
+//The keyboard layouts and variants xkb knows about, for the input page
+
+pub const EVDEV_LST: &str = "/usr/share/X11/xkb/rules/evdev.lst";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XkbLayout {
+    pub code: String, //what kb_layout takes, like us
+    pub description: String,
+}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XkbVariant {
+    pub layout: String, //the layout code it belongs to
+    pub code: String, //what kb_variant takes, like intl
+    pub description: String,
+}
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct XkbRules {
+    pub layouts: Vec<XkbLayout>,
+    pub variants: Vec<XkbVariant>,
+}
+impl XkbRules {
+    pub fn variants_of(&self, layout: &str) -> Vec<XkbVariant> {
+        self.variants.iter().filter(|x| x.layout == layout).cloned().collect()
+    }
+}
+
+pub fn parse_evdev_lst(contents: &str) -> XkbRules { //sections start with a ! line, entries are the code, spaces and a description
+    let mut rules = XkbRules::default();
+    let mut section = "";
+    for line in contents.lines() {
+        if let Some(name) = line.strip_prefix('!') {
+            section = name.trim();
+            continue;
+        }
+        let (code, description) = match line.trim().split_once(char::is_whitespace) {
+            Some((code, description)) => (code, description.trim()),
+            None => continue
+        };
+        match section {
+            "layout" => rules.layouts.push(XkbLayout {
+                code: code.to_string(),
+                description: description.to_string(),
+            }),
+            "variant" => match description.split_once(": ") { //the description starts with the layout code
+                Some((layout, description)) => rules.variants.push(XkbVariant {
+                    layout: layout.to_string(),
+                    code: code.to_string(),
+                    description: description.to_string(),
+                }),
+                None => continue
+            },
+            _ => continue
+        }
+    }
+    rules
+}
+pub fn read_xkb_rules() -> Result<XkbRules, CfgError> { //no xkb data is not an error, the page falls back to typing the codes
+    match fs::read_to_string(EVDEV_LST) {
+        Ok(contents) => Ok(parse_evdev_lst(&contents)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(XkbRules::default()),
+        Err(e) => Err(CfgError::Io(EVDEV_LST.to_string(), e))
+    }
+}
+
+impl std::fmt::Display for XkbLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.description)
+    }
+}
+impl std::fmt::Display for XkbVariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.description)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evdev_lst_is_read() {
+        let rules = parse_evdev_lst(include_str!("../tests/fixtures/evdev.lst"));
+        assert_eq!(rules.layouts.len(), 3);
+        assert_eq!(rules.layouts[0], XkbLayout {code: "us".to_string(), description: "English (US)".to_string()});
+        assert_eq!(rules.variants.len(), 5); //models and options are skipped
+        let german = rules.variants_of("de");
+        assert_eq!(german.len(), 2);
+        assert_eq!(german[0], XkbVariant {layout: "de".to_string(), code: "nodeadkeys".to_string(), description: "German (no dead keys)".to_string()});
+        assert_eq!(german[1].code, "us");
+        assert!(rules.variants_of("zz").is_empty());
+    }
+}
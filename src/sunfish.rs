@@ -2,7 +2,7 @@ use iced::theme::Theme;
 use iced::{Result, Settings, alignment, Alignment, Length, Application, Command, executor};
 use iced::widget::{Button, Row, Column, Container, Text, Scrollable};
 use iced::Color;
-use lib_cfg::{get_cfg_data, decode_theme};
+use lib_cfg::{peek_cfg_data, decode_theme, encode, Dispatcher, FileData, Keybind, Workspaces, WorkspaceKeys, Directions, DirectionKeys, Layout, LayoutEngine, PSEUDO_KEY, SPLIT_KEY, SWAP_MASTER_KEY, rip_key};
 mod lib_cfg;
 
 use gettextrs::*;
@@ -18,18 +18,14 @@ fn main() -> Result {
 struct Manual {
     theme:SelectedTheme,
     current_page:u8,
-    primary_key: String,
-    secondary_key: String,
-    exit_header: String,
-    exit_key: String,
-    launch_header: String,
-    launch_key: String,
-    kill_header: String,
-    kill_key: String,
-    minimize_header: String,
-    minimize_key: String,
-    scratch_header: String,
-    scratch_key: String,
+    navigation_text: String,
+    exit_bind: String,
+    launch_bind: String,
+    kill_bind: String,
+    minimize_bind: String,
+    scratch_bind: String,
+    workspace_text: String,
+    layout_text: String,
     theme_set: ThemeSet,
 }
 pub fn pretty_pri(x: &str) -> &'static str {
@@ -38,6 +34,9 @@ pub fn pretty_pri(x: &str) -> &'static str {
         "alt" => "Alt",
         "control" => "Control",
         "shift" => "Shift",
+        "mod3" => "Hyper",
+        "mod5" => "AltGr",
+        "caps" => "Caps Lock",
         &_ => "Error"
     }
 }
@@ -48,30 +47,114 @@ pub fn pretty_header(x: &str, pri: &str, sec: &str) -> String {
         "pri" => primary,
         "sec" => secondary,
         "both" => format!("{primary}+{secondary}"),
+        "none" => String::new(),
         &_ => "Error".to_string()
     }
 }
+pub fn pretty_keybind(binds: &Vec<Keybind>, dispatcher: Dispatcher, arg: &str, pri: &str, sec: &str) -> String { //describes the first binding that does the given thing
+    for bind in binds {
+        if bind.dispatcher == dispatcher && bind.arg.trim() == arg {
+            let mut keys = vec![];
+            let header = pretty_header(&encode(&bind.header), pri, sec);
+            if header.len() > 0 {
+                keys.push(header);
+            }
+            for i in 0..bind.mods.len() {
+                keys.push(pretty_pri(&encode(&bind.mods[i])).to_string());
+            }
+            keys.push(bind.key.clone());
+            return keys.join("+");
+        }
+    }
+    tr("(not bound)")
+}
+
+pub fn pretty_directions(directions: &Directions, pri: &str, sec: &str) -> String { //the first page, written from the same settings hyprland.conf is
+    let key = match directions.keys {
+        DirectionKeys::Arrows => tr("An Arrow Key"),
+        DirectionKeys::Vim => tr("H, J, K or L"),
+        DirectionKeys::Wasd => tr("W, A, S or D"),
+    };
+    let mut text = format!("{}{pri}+{key}.\n{}", tr("To shift focus between applications, press:\n"), tr("This will shift the interface's focus in the direction you pressed.\n \n"));
+    text = format!("{text}{}{pri}+{sec}+{key}.\n{}", tr("To move applications around, press:\n"), tr("This should swap applications in that direction.\n\n"));
+    if directions.resize {
+        let resize_mod = pretty_pri(&encode(&directions.resize_mod));
+        let keys = if resize_mod == pri {pri.to_string()} else {format!("{pri}+{resize_mod}")}; //the primary key changed to the resize key since
+        text = format!("{text}{}{keys}+{key}.\n{}", tr("To resize the focused application, press:\n"), tr("Holding the keys keeps resizing it.\n\n"));
+    }
+    if directions.submap {
+        text = format!("{text}{}{pri}+{}.\n{}{key}{}", tr("To start resizing without holding anything, press:\n"), rip_key(&directions.submap_key), tr("Then resize the focused application with "), tr(" alone, and press Escape when you are done.\n\n"));
+    }
+    format!("{text}{}", tr("You can change these on the Keybindings page of the configurator."))
+}
+pub fn pretty_workspaces(workspaces: &Workspaces, pri: &str, sec: &str) -> String { //the workspace page, written from the same settings hyprland.conf is
+    let count = workspaces.count();
+    let key = match workspaces.keys {
+        WorkspaceKeys::Numbers => tr("A Number Key"),
+        WorkspaceKeys::Function => tr("A Function Key"),
+        WorkspaceKeys::Numpad => tr("A Number Pad Key"),
+    };
+    let mut text = format!("{}{count}{}", tr("There are "), tr(" workspaces in this environment-\n In effect each one is its own desktop where you can move applications to or visit the applications located there.\n\n"));
+    text = format!("{text}{}{pri}+{key}.\n{}", tr("To move yourself to a workspace, press:\n"), tr("This will move you to the workspace corresponding to the key you pressed.\n \n"));
+    let post_move = if workspaces.follow {
+        tr("This will send the application to the corresponding workspace and take you along with it.\n\n")
+    } else {
+        tr("This will banish the application to the corresponding workspace.\n\n")
+    };
+    text = format!("{text}{}{pri}+{sec}+{key}.\n{post_move}", tr("To move the currently focused application to a workspace, press:\n"));
+    if workspaces.cycle {
+        text = format!("{text}{}{pri}+.{}{pri}+,{}", tr("To go to the next workspace, press "), tr(", or to go to the previous one, press "), tr(". Scrolling the mouse wheel while holding the key does the same.\n\n"));
+    }
+    for monitor in &workspaces.monitors {
+        if (monitor.workspace as usize) <= count {
+            text = format!("{text}{}{}{}{}.\n", tr("Workspace "), monitor.workspace, tr(" always opens on "), monitor.monitor.trim());
+        }
+    }
+    if workspaces.monitors.len() > 0 {
+        text = format!("{text}\n");
+    }
+    format!("{text}{}", tr("You can change these on the Workspaces page of the configurator."))
+}
+pub fn pretty_layout(layout: &Layout, pri: &str) -> String { //the layout page, only the binds of the engine in use exist
+    let text = match layout.engine {
+        LayoutEngine::Dwindle => {
+            let mut text = format!("{}{pri}+{}.\n{}", tr("Windows are tiled by splitting the space in two, again and again. To flip the focused window's split between side by side and on top of each other, press:\n"), rip_key(SPLIT_KEY), tr("This turns the split of the focused window around.\n\n"));
+            if layout.pseudotile {
+                text = format!("{text}{}{pri}+{}.\n{}", tr("To let the focused application keep its own size inside its tile, press:\n"), rip_key(PSEUDO_KEY), tr("Press it again to have it fill the tile.\n\n"));
+            }
+            text
+        }
+        LayoutEngine::Master => format!("{}{pri}+{}.\n{}", tr("One large master window sits beside a stack of the others. To swap the focused window with the master window, press:\n"), rip_key(SWAP_MASTER_KEY), tr("The master window then takes its place in the stack.\n\n")),
+    };
+    format!("{text}{}", tr("You can change these on the Window Layout page of the configurator."))
+}
 
 impl Default for Manual {
     fn default() -> Self {
-        let data = get_cfg_data();
-        let pri = pretty_pri(&data.primary);
-        let sec = pretty_pri(&data.secondary);
+        let (data, warnings) = match peek_cfg_data() { //only reads, opening the manual never rewrites cfg.toml
+            Ok(loaded) => loaded,
+            Err(e) => {
+                eprintln!("{e}");
+                (FileData::default(), vec![])
+            }
+        };
+        for warning in warnings {
+            eprintln!("cfg.toml: {warning}");
+        }
+        let pri = pretty_pri(&encode(&data.primary));
+        let sec = pretty_pri(&encode(&data.secondary));
+        let binds = data.binds.clone();
         Manual {
-            theme: decode_theme(&data.theme, SelectedTheme::Light),
+            theme: decode_theme(data.theme),
             current_page: 0,
-            primary_key: pri.to_string(),
-            secondary_key: sec.to_string(),
-            exit_header: pretty_header(&data.exit_h, pri, sec).to_string(),
-            exit_key: data.exit_k,
-            launch_header: pretty_header(&data.launch_h, pri, sec).to_string(),
-            launch_key: data.launch_k,
-            kill_header: pretty_header(&data.kill_h, pri, sec).to_string(),
-            kill_key: data.kill_k,
-            minimize_header: pretty_header(&data.mini_h, pri, sec).to_string(),
-            minimize_key: data.mini_k,
-            scratch_header: pretty_header(&data.scratch_h, pri, sec).to_string(),
-            scratch_key: data.scratch_k,
+            navigation_text: pretty_directions(&data.directions, pri, sec),
+            exit_bind: pretty_keybind(&binds, Dispatcher::Exec, "wlogout", pri, sec),
+            launch_bind: pretty_keybind(&binds, Dispatcher::Exec, "rofi -show drun", pri, sec),
+            kill_bind: pretty_keybind(&binds, Dispatcher::KillActive, "", pri, sec),
+            minimize_bind: pretty_keybind(&binds, Dispatcher::MoveToWorkspace, "special", pri, sec),
+            scratch_bind: pretty_keybind(&binds, Dispatcher::ToggleSpecialWorkspace, "", pri, sec),
+            workspace_text: pretty_workspaces(&data.workspaces, pri, sec),
+            layout_text: pretty_layout(&data.layout, pri),
             theme_set: ThemeSet {
                 light: ThemeCustom {
                     application: iced::theme::Palette {
@@ -185,7 +268,7 @@ impl Application for Manual {
     fn update(&mut self, message: Self::Message) -> Command<Message> {
         match message {
             Message::PageIncr => {
-                if self.current_page < 3 {self.current_page = self.current_page + 1;}
+                if self.current_page < 4 {self.current_page = self.current_page + 1;}
                 Command::none()
             }
             Message::PageDecr => {
@@ -196,7 +279,7 @@ impl Application for Manual {
                 match x {
                     iced::keyboard::Event::KeyPressed { key_code, modifiers: _ } => {
                         if key_code == iced::keyboard::KeyCode::Right {
-                            if self.current_page < 3 {self.current_page = self.current_page + 1;}
+                            if self.current_page < 4 {self.current_page = self.current_page + 1;}
                         } else if key_code == iced::keyboard::KeyCode::Left {
                             if self.current_page > 0 {self.current_page = self.current_page -1;}
                         }
@@ -238,26 +321,14 @@ impl Application for Manual {
             page_left = page_left.style(style.secondary.mk_theme());
             let title = tr("Basic Navigation");
             pg_title = Text::new(format!("{title}"));
-            let primary_key = self.primary_key.clone();
-            let secondary_key = self.secondary_key.clone();
-            let pre_focus = tr("To shift focus between applications, press:\n");
-            let focus = tr("+An Arrow Key.\n");
-            let post_focus = tr("This will shift the interface's focus in the direction you pressed.\n \n");
-            let pre_move = tr("To move applications around, press:\n");
-            let move_str = tr("+An Arrow Key.\n");
-            let post_move = tr("This should swap applications in that direction.\n\n");
-            let immutable = tr("As of now, these bindings are inferred and cannot be directly changed.");
-            let text = Text::new(format!("{pre_focus}{primary_key}{focus}{post_focus}{pre_move}{primary_key}+{secondary_key}{move_str}{post_move}{immutable}")).horizontal_alignment(alignment::Horizontal::Center);
+            let text = Text::new(self.navigation_text.clone()).horizontal_alignment(alignment::Horizontal::Center);
             settings = settings.push(text);
         } else if self.current_page == 1 {
             let title = tr("Basic Navigation, Continued");
             pg_title = Text::new(format!("{title}"));
-            let launch_h = self.launch_header.clone();
-            let launch_k = self.launch_key.clone();
-            let kill_h = self.kill_header.clone();
-            let kill_k = self.kill_key.clone();
-            let exit_h = self.exit_header.clone();
-            let exit_k = self.exit_key.clone();
+            let launch = self.launch_bind.clone();
+            let kill_bind = self.kill_bind.clone();
+            let exit_bind = self.exit_bind.clone();
             let pre_search = tr("To open the application search, press:\n");
             let search = tr(".\n");
             let post_search = tr("This will open a search menu that you can use to run the apps you want.\n\n");
@@ -267,38 +338,31 @@ impl Application for Manual {
             let pre_exit = tr("To return to the login screen, press:\n");
             let exit = tr(".\n");
             let post_exit = tr("This will close out the desktop entirely, destroying all unsaved work.");
-            let text = Text::new(format!("{pre_search}{launch_h}+{launch_k}{search}{post_search}{pre_kill}{kill_h}+{kill_k}{kill}{post_kill}{pre_exit}{exit_h}+{exit_k}{exit}{post_exit}")).horizontal_alignment(alignment::Horizontal::Center);
+            let text = Text::new(format!("{pre_search}{launch}{search}{post_search}{pre_kill}{kill_bind}{kill}{post_kill}{pre_exit}{exit_bind}{exit}{post_exit}")).horizontal_alignment(alignment::Horizontal::Center);
             settings = settings.push(text);
         } else if self.current_page == 2 {
             let title = tr("Workspaces");
             pg_title = Text::new(title);
-            let primary_key = self.primary_key.clone();
-            let secondary_key = self.secondary_key.clone();
-            let head = tr("There are 10 workspaces in this environment-\n In effect each one is its own desktop where you can move applications to or visit the applications located there.\n\n");
-            let pre_focus = tr("To move yourself to a workspace, press:\n");
-            let focus = tr("+A Number Key.\n");
-            let post_focus = tr("This will move you to the workspace corresponding to the number you pressed.\n \n");
-            let pre_move = tr("To move the currently focused application to a workspace, press:\n");
-            let move_txt = tr("+A Number Key.\n");
-            let post_move = tr("This will banish the application to the corresponding workspace.\n\n");
-            let immutable = tr("As of now, these bindings are inferred and cannot be directly changed.");
-            let text = Text::new(format!("{head}{pre_focus}{primary_key}{focus}{post_focus}{pre_move}{primary_key}+{secondary_key}{move_txt}{post_move}{immutable}")).horizontal_alignment(alignment::Horizontal::Center);
+            let text = Text::new(self.workspace_text.clone()).horizontal_alignment(alignment::Horizontal::Center);
             settings = settings.push(text);
         } else if self.current_page == 3 {
+            let title = tr("Window Layout");
+            pg_title = Text::new(title);
+            let text = Text::new(self.layout_text.clone()).horizontal_alignment(alignment::Horizontal::Center);
+            settings = settings.push(text);
+        } else if self.current_page == 4 {
             page_right = page_right.style(style.secondary.mk_theme());
             let title = tr("Minimization");
             pg_title = Text::new(title);
-            let mini_h = self.minimize_header.clone();
-            let mini_k = self.minimize_key.clone();
-            let scratch_h = self.scratch_header.clone();
-            let scratch_k = self.scratch_key.clone();
+            let mini = self.minimize_bind.clone();
+            let scratch = self.scratch_bind.clone();
             let pre_move = tr("To minimize the focused application, press:\n");
             let move_txt = tr(".\n");
             let post_move = tr("This will minimize said application, temporarily removing it from the current workspace.\n\n");
             let pre_focus = tr("To show the currently minimized apps, press:\n");
             let focus = tr(".\n");
             let post_focus = tr("This show all of your minimized apps.");
-            let text = Text::new(format!("{pre_move}{mini_h}+{mini_k}{move_txt}{post_move}{pre_focus}{scratch_h}+{scratch_k}{focus}{post_focus}")).horizontal_alignment(alignment::Horizontal::Center);
+            let text = Text::new(format!("{pre_move}{mini}{move_txt}{post_move}{pre_focus}{scratch}{focus}{post_focus}")).horizontal_alignment(alignment::Horizontal::Center);
             settings = settings.push(text);
         }
         let left_col = Column::new().width(Length::FillPortion(2))